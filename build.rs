@@ -25,9 +25,17 @@ fn has_allocator_api() -> bool {
     cfg!(feature = "allocator_api")
 }
 
+/// Whether `core::error::Error` is available (Rust 1.81 and later).
+fn has_core_error() -> bool {
+    autocfg::new().probe_path("core::error::Error")
+}
+
 fn main() {
     if has_allocator_api() {
         println!("cargo:rustc-cfg=has_allocator_api");
     }
+    if has_core_error() {
+        println!("cargo:rustc-cfg=has_core_error");
+    }
     println!("cargo:rerun-if-changed=build.rs");
 }
@@ -0,0 +1,34 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Builds the C API of fixed-bump (see `fixed_bump::ffi`) as a static
+//! library (`libfixed_bump_ffi.a`) and a shared library
+//! (`libfixed_bump_ffi.so` or the platform's equivalent):
+//!
+//! ```text
+//! cargo build --release --manifest-path ffi/Cargo.toml
+//! ```
+//!
+//! The tests of this crate check that the C header `include/fixed_bump.h` is
+//! up to date with `src/ffi.rs`. To regenerate it with cbindgen, run
+//! `UPDATE_HEADER=1 cargo test` in this directory.
+
+#![no_std]
+
+pub use fixed_bump::ffi::*;
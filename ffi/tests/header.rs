@@ -0,0 +1,50 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Checks that `include/fixed_bump.h` matches the header cbindgen generates
+/// from `src/ffi.rs`. Run with `UPDATE_HEADER=1` to regenerate it.
+#[test]
+fn header_up_to_date() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = dir.parent().unwrap();
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml"))
+        .expect("could not read cbindgen.toml");
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src").join("ffi.rs"))
+        .generate()
+        .expect("could not generate the C header")
+        .write(&mut generated);
+    let path = root.join("include").join("fixed_bump.h");
+    if env::var_os("UPDATE_HEADER").is_some() {
+        fs::write(&path, &generated).expect("could not write the C header");
+        return;
+    }
+    let current = fs::read(&path).expect("could not read the C header");
+    assert!(
+        current == generated,
+        "include/fixed_bump.h is out of date; run `UPDATE_HEADER=1 cargo \
+         test` in the `ffi` directory to regenerate it",
+    );
+}
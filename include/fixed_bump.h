@@ -0,0 +1,177 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/* C API for fixed-bump. Build the library with
+ *
+ *     cargo build --release --manifest-path ffi/Cargo.toml
+ *
+ * and link against `libfixed_bump_ffi`. See the documentation of the
+ * `fixed_bump::ffi` module for details.
+ */
+
+#ifndef FIXED_BUMP_H
+#define FIXED_BUMP_H
+
+/* This file is generated from `src/ffi.rs` by cbindgen. Do not edit it by
+ * hand; run `UPDATE_HEADER=1 cargo test` in the `ffi` directory instead. */
+
+#include <stddef.h>
+
+/*
+ The result of the most recent allocation; see [`fixed_bump_last_error`].
+
+ In C, this is `fixed_bump_status`.
+ */
+typedef enum fixed_bump_status {
+  /*
+   The allocation succeeded.
+   */
+  FIXED_BUMP_STATUS_OK = 0,
+  /*
+   The requested size or alignment is larger than that of a chunk, so
+   the allocation can never succeed.
+   */
+  FIXED_BUMP_STATUS_TOO_LARGE = 1,
+  /*
+   A new chunk was needed, but the global allocator failed to allocate
+   it.
+   */
+  FIXED_BUMP_STATUS_OUT_OF_MEMORY = 2,
+  /*
+   The requested size and alignment don't form a valid layout: the
+   alignment isn't a power of two, or the size is too large.
+   */
+  FIXED_BUMP_STATUS_INVALID_LAYOUT = 3,
+  /*
+   The allocation failed for another reason, such as the allocator being
+   sealed or a memory limit being reached.
+   */
+  FIXED_BUMP_STATUS_OTHER = 4,
+} fixed_bump_status;
+
+/*
+ An allocator created by [`fixed_bump_new`].
+
+ In C, this is the opaque type `fixed_bump`.
+ */
+typedef struct fixed_bump fixed_bump;
+
+/*
+ Statistics about an allocator; see [`fixed_bump_get_stats`].
+
+ In C, this is `fixed_bump_stats`.
+ */
+typedef struct fixed_bump_stats {
+  /*
+   The size of each chunk.
+   */
+  size_t chunk_size;
+  /*
+   The alignment of each chunk.
+   */
+  size_t chunk_align;
+  /*
+   The total size of the chunks in use since the allocator was created
+   or last reset, including the bookkeeping data stored alongside each
+   chunk.
+   */
+  size_t chunk_bytes;
+  /*
+   The number of chunks kept for reuse after a reset.
+   */
+  size_t free_chunks;
+  /*
+   The number of bytes left in the current chunk.
+   */
+  size_t remaining;
+} fixed_bump_stats;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Creates a new allocator whose chunks have the given size and alignment.
+
+ Returns a null pointer if `size` is zero, `align` isn't a power of two, or
+ the chunk layout is too large.
+ */
+struct fixed_bump *fixed_bump_new(size_t size, size_t align);
+
+/*
+ Allocates memory with the given size and alignment.
+
+ Returns a pointer to the memory, which is valid until the allocator is
+ reset or freed, or a null pointer on failure. Use [`fixed_bump_last_error`]
+ to find out why an allocation failed.
+
+ # Safety
+
+ `bump` must have been returned by [`fixed_bump_new`] and not yet freed.
+ */
+void *fixed_bump_alloc(struct fixed_bump *bump, size_t size, size_t align);
+
+/*
+ Returns the result of the most recent call to [`fixed_bump_alloc`], or
+ [`Status::Ok`] if there hasn't been one.
+
+ # Safety
+
+ `bump` must have been returned by [`fixed_bump_new`] and not yet freed.
+ */
+enum fixed_bump_status fixed_bump_last_error(const struct fixed_bump *bump);
+
+/*
+ Frees all memory allocated so far, so that it can be reused. The
+ allocator's chunks are kept for future allocations rather than
+ deallocated.
+
+ # Safety
+
+ `bump` must have been returned by [`fixed_bump_new`] and not yet freed,
+ and memory previously allocated with it must no longer be used.
+ */
+void fixed_bump_reset(struct fixed_bump *bump);
+
+/*
+ Writes statistics about the allocator to `stats`.
+
+ # Safety
+
+ `bump` must have been returned by [`fixed_bump_new`] and not yet freed,
+ and `stats` must be valid for writes.
+ */
+void fixed_bump_get_stats(const struct fixed_bump *bump, struct fixed_bump_stats *stats);
+
+/*
+ Frees the allocator and all memory allocated with it. Does nothing if
+ `bump` is null.
+
+ # Safety
+
+ `bump` must be null or have been returned by [`fixed_bump_new`] and not
+ yet freed, and memory allocated with it must no longer be used.
+ */
+void fixed_bump_free(struct fixed_bump *bump);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FIXED_BUMP_H */
@@ -0,0 +1,192 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+struct SharedBudget {
+    limit: usize,
+    used: AtomicUsize,
+}
+
+/// A memory budget that can be shared by multiple bump allocators.
+///
+/// Allocators created with a budget (e.g., with [`Bump::with_budget`]) draw
+/// the size of each chunk they obtain from the budget, and give it back when
+/// they are dropped. Once the budget is exhausted, none of those allocators
+/// can obtain new chunks, so the total size of their chunks is bounded by
+/// [`Self::limit`].
+///
+/// This type is a reference-counted handle; cloning it produces another
+/// handle to the same budget. Handles can be sent to and shared between
+/// threads.
+///
+/// [`Bump::with_budget`]: crate::Bump::with_budget
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "alloc", target_has_atomic = "ptr")))
+)]
+#[derive(Clone)]
+pub struct Budget(Arc<SharedBudget>);
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl Budget {
+    /// Creates a new [`Budget`] that allows chunks with a total size of at
+    /// most `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self(Arc::new(SharedBudget {
+            limit,
+            used: AtomicUsize::new(0),
+        }))
+    }
+
+    /// The maximum total size, in bytes, of the chunks drawn from this
+    /// budget.
+    pub fn limit(&self) -> usize {
+        self.0.limit
+    }
+
+    /// The total size, in bytes, of the chunks currently drawn from this
+    /// budget.
+    ///
+    /// This may be greater than [`Self::limit`] if an allocator that already
+    /// had chunks was switched to this budget (see [`Bump::set_budget`]).
+    ///
+    /// [`Bump::set_budget`]: crate::Bump::set_budget
+    pub fn used(&self) -> usize {
+        self.0.used.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes that can still be drawn from this budget.
+    pub fn remaining(&self) -> usize {
+        self.limit().saturating_sub(self.used())
+    }
+
+    /// Draws `size` bytes from the budget, unless that would exceed the
+    /// limit. Returns whether the bytes were drawn.
+    fn try_charge(&self, size: usize) -> bool {
+        let limit = self.0.limit;
+        self.0
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(size).filter(|&n| n <= limit)
+            })
+            .is_ok()
+    }
+
+    /// Draws `size` bytes from the budget even if that exceeds the limit.
+    fn charge(&self, size: usize) {
+        self.0.used.fetch_add(size, Ordering::Relaxed);
+    }
+
+    fn release(&self, size: usize) {
+        self.0.used.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+/// The limits on the chunks owned by a single allocator, and the number of
+/// bytes and chunks counted against them.
+#[derive(Default)]
+pub struct Limits {
+    max_bytes: Option<usize>,
+    max_chunks: Option<usize>,
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    budget: Option<Budget>,
+    bytes: usize,
+    chunks: usize,
+}
+
+impl Limits {
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    pub fn set_max_bytes(&mut self, max: Option<usize>) {
+        self.max_bytes = max;
+    }
+
+    pub fn max_chunks(&self) -> Option<usize> {
+        self.max_chunks
+    }
+
+    pub fn set_max_chunks(&mut self, max: Option<usize>) {
+        self.max_chunks = max;
+    }
+
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub fn budget(&self) -> Option<&Budget> {
+        self.budget.as_ref()
+    }
+
+    /// Moves the bytes counted so far from the old budget to the new one.
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub fn set_budget(&mut self, budget: Option<Budget>) {
+        if let Some(old) = &self.budget {
+            old.release(self.bytes);
+        }
+        if let Some(new) = &budget {
+            new.charge(self.bytes);
+        }
+        self.budget = budget;
+    }
+
+    /// The total size of the chunks currently counted.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Counts a new chunk of `size` bytes, unless that would exceed a limit.
+    /// Returns whether the chunk was counted.
+    pub fn try_charge(&mut self, size: usize) -> bool {
+        let bytes = if let Some(bytes) = self.bytes.checked_add(size) {
+            bytes
+        } else {
+            return false;
+        };
+        if self.max_bytes.map_or(false, |max| bytes > max)
+            || self.max_chunks.map_or(false, |max| self.chunks >= max)
+        {
+            return false;
+        }
+        #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+        if let Some(budget) = &self.budget {
+            if !budget.try_charge(size) {
+                return false;
+            }
+        }
+        self.bytes = bytes;
+        self.chunks += 1;
+        true
+    }
+
+    /// Stops counting a chunk of `size` bytes previously counted with
+    /// [`Self::try_charge`].
+    pub fn release(&mut self, size: usize) {
+        self.bytes -= size;
+        self.chunks -= 1;
+        #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+        if let Some(budget) = &self.budget {
+            budget.release(size);
+        }
+    }
+}
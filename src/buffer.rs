@@ -0,0 +1,190 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::chunk::Chunk;
+use super::source::ChunkSource;
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ptr::NonNull;
+
+/// A block that isn't in use. The link to the next free block is stored in
+/// the block itself.
+struct FreeBlock {
+    next: Option<NonNull<FreeBlock>>,
+}
+
+/// A [`ChunkSource`] that splits caller-provided memory into fixed-size
+/// blocks, each of which holds one chunk.
+///
+/// This lets bump allocators run without a global allocator (see the crate
+/// feature `alloc`), or use memory that was reserved ahead of time. Memory is
+/// added with [`Self::add_buffer`], which can be called any number of times.
+/// Once every block is in use, the source fails to allocate, so the bump
+/// allocator fails the same way it would if the global allocator failed:
+/// for example, [`Bump::try_alloc_value`] returns an error. Chunks returned
+/// to the source, such as when a bump allocator is dropped, are reused.
+///
+/// ```
+/// use core::alloc::Layout;
+/// use core::mem::MaybeUninit;
+/// use fixed_bump::{BufferSource, Bump};
+///
+/// let mut memory = [MaybeUninit::uninit(); 512];
+/// let source = BufferSource::new(Layout::new::<[u64; 8]>());
+/// let blocks = source.add_buffer(&mut memory);
+/// assert!(blocks > 0);
+///
+/// let bump = Bump::<[u64; 8], u64, _>::new_in(&source);
+/// let mut n = 0;
+/// while bump.try_alloc_value([0_u64; 8]).is_ok() {
+///     n += 1;
+/// }
+/// assert_eq!(n, blocks);
+/// drop(bump);
+/// assert_eq!(source.free_chunks(), blocks);
+/// ```
+///
+/// [`Bump::try_alloc_value`]: crate::Bump::try_alloc_value
+pub struct BufferSource<'a> {
+    block: Layout,
+    free: Cell<Option<NonNull<FreeBlock>>>,
+    len: Cell<usize>,
+    phantom: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+impl<'a> BufferSource<'a> {
+    /// Creates a new [`BufferSource`] with no memory, for bump allocators
+    /// whose chunks have layout `chunk_layout` (the layout of `Size` with the
+    /// alignment of `Align` for [`Bump`], or the layout passed to
+    /// [`DynamicBump::new_in`]).
+    ///
+    /// Each block is large enough for a chunk with that layout, including a
+    /// small amount of bookkeeping data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of a block would overflow.
+    ///
+    /// [`Bump`]: crate::Bump
+    /// [`DynamicBump::new_in`]: crate::DynamicBump::new_in
+    pub fn new(chunk_layout: Layout) -> Self {
+        let full = Chunk::full_layout(chunk_layout);
+        let block = Layout::from_size_align(
+            full.size().max(mem::size_of::<FreeBlock>()),
+            full.align().max(mem::align_of::<FreeBlock>()),
+        )
+        .expect("chunk layout is too large")
+        .pad_to_align();
+        Self {
+            block,
+            free: Cell::new(None),
+            len: Cell::new(0),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Like [`Self::new`], but also adds `buffer` (see [`Self::add_buffer`]).
+    pub fn with_buffer(
+        chunk_layout: Layout,
+        buffer: &'a mut [MaybeUninit<u8>],
+    ) -> Self {
+        let source = Self::new(chunk_layout);
+        source.add_buffer(buffer);
+        source
+    }
+
+    /// Splits `buffer` into blocks and makes them available for chunks.
+    /// Returns the number of blocks added, which is zero if `buffer` is too
+    /// small to hold a single (properly aligned) block.
+    pub fn add_buffer(&self, buffer: &'a mut [MaybeUninit<u8>]) -> usize {
+        let size = self.block.size();
+        let start = buffer.as_mut_ptr().cast::<u8>();
+        let padding =
+            (start as usize).wrapping_neg() & (self.block.align() - 1);
+        let n = buffer.len().saturating_sub(padding) / size;
+        for i in 0..n {
+            // SAFETY: `padding + (i + 1) * size` is at most `buffer.len()`,
+            // so the block is within `buffer`.
+            let block = unsafe { start.add(padding + i * size) };
+            // SAFETY: `block` is derived from a non-null pointer and is
+            // properly aligned for `FreeBlock`, since `self.block` is.
+            unsafe {
+                self.push(NonNull::new_unchecked(block).cast());
+            }
+        }
+        n
+    }
+
+    /// The number of blocks that aren't in use.
+    pub fn free_chunks(&self) -> usize {
+        self.len.get()
+    }
+
+    /// # Safety
+    ///
+    /// `block` must point to a block of memory matching `self.block` that is
+    /// valid for `'a` and not in use.
+    unsafe fn push(&self, block: NonNull<FreeBlock>) {
+        // SAFETY: Checked by caller.
+        unsafe {
+            block.as_ptr().write(FreeBlock {
+                next: self.free.get(),
+            });
+        }
+        self.free.set(Some(block));
+        self.len.set(self.len.get() + 1);
+    }
+
+    fn pop(&self) -> Option<NonNull<u8>> {
+        let block = self.free.get()?;
+        // SAFETY: Blocks in the free list hold valid `FreeBlock`s.
+        self.free.set(unsafe { (*block.as_ptr()).next });
+        self.len.set(self.len.get() - 1);
+        Some(block.cast())
+    }
+}
+
+// SAFETY: A `BufferSource` exclusively borrows its buffers, like a
+// `&'a mut [MaybeUninit<u8>]`, which is `Send`.
+unsafe impl Send for BufferSource<'_> {}
+
+// SAFETY: Each block is a separate part of a buffer borrowed for `'a`, which
+// outlives `self`, so moving `self` doesn't invalidate it. A block isn't
+// handed out again until it is deallocated. Neither method calls into any
+// bump allocator.
+unsafe impl ChunkSource for BufferSource<'_> {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if layout.size() > self.block.size()
+            || layout.align() > self.block.align()
+        {
+            return None;
+        }
+        self.pop()
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, _layout: Layout) {
+        // SAFETY: The caller guarantees `ptr` was returned by
+        // `Self::allocate`, so it is a block that is no longer in use.
+        unsafe {
+            self.push(ptr.cast());
+        }
+    }
+}
@@ -17,36 +17,38 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use super::generic::{GenericBump, IntoLayout};
-#[cfg(any(feature = "allocator_api", feature = "allocator-fallback"))]
-use super::{AllocError, Allocator};
-use alloc::alloc::Layout;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use super::budget::Budget;
+use super::generic::{ChunkLayout, ConstChunkLayout, GenericBump};
+use super::methods::{bump_methods, const_layout_methods};
+#[cfg(feature = "std")]
+use super::pool::SyncChunkPool;
+#[cfg(feature = "alloc")]
+use super::pool::{ChunkPool, Pool};
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
 use core::marker::PhantomData;
 use core::mem;
-use core::ptr::NonNull;
 
-struct ConstLayout<Size, Align>(PhantomData<fn() -> (Size, Align)>);
-
-impl<Size, Align> Clone for ConstLayout<Size, Align> {
-    fn clone(&self) -> Self {
-        *self
-    }
+pub struct ConstLayout<Size, Align>(pub PhantomData<fn() -> (Size, Align)>);
+
+impl<Size, Align> ConstChunkLayout for ConstLayout<Size, Align> {
+    const LAYOUT: Layout = {
+        let size = mem::size_of::<Size>();
+        assert!(size != 0, "`Size` must not be zero-sized");
+        match Layout::from_size_align(size, mem::align_of::<Align>()) {
+            Ok(layout) => layout,
+            Err(_) => panic!("chunk layout is too large"),
+        }
+    };
 }
 
-impl<Size, Align> Copy for ConstLayout<Size, Align> {}
-
-impl<Size, Align> From<ConstLayout<Size, Align>> for Layout {
-    fn from(_: ConstLayout<Size, Align>) -> Self {
-        Self::from_size_align(mem::size_of::<Size>(), mem::align_of::<Align>())
-            .unwrap()
+impl<Size, Align> ChunkLayout for ConstLayout<Size, Align> {
+    fn chunk_layout(&self) -> Layout {
+        Self::LAYOUT
     }
 }
 
-// SAFETY: `<Self as Into<Layout>>::into` forwards to
-// `<Layout as From<Self>>:from`, which does not run any code that could call
-// methods of any [`GenericBump`].
-unsafe impl<Size, Align> IntoLayout for ConstLayout<Size, Align> {}
-
 /// A bump allocator that allocates memory in non-amortized O(1) (constant)
 /// time.
 ///
@@ -63,117 +65,149 @@ unsafe impl<Size, Align> IntoLayout for ConstLayout<Size, Align> {}
 /// using an array type: to use properly aligned chunks large enough to
 /// allocate `n` values of type `T`, pass `[T; n]` as the `Size` parameter,
 /// which will also be the `Align` parameter by default.
-pub struct Bump<Size, Align = Size>(GenericBump<ConstLayout<Size, Align>>);
-
+///
+/// To make chunks large and aligned enough for arrays of several different
+/// types, use the [`chunk_for!`] macro. Zero-sized marker types like
+/// [`Align4096`] can be used as the `Align` parameter to request a specific
+/// alignment.
+///
+/// Chunks are allocated with the global allocator by default. The optional
+/// type parameter `A` specifies a different [`ChunkSource`]; see
+/// [`Self::new_in`].
+///
+/// [`chunk_for!`]: crate::chunk_for
+/// [`Align4096`]: crate::Align4096
+pub struct Bump<Size, Align = Size, A: ChunkSource = Global>(
+    GenericBump<ConstLayout<Size, Align>, A>,
+);
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
 impl<Size, Align> Bump<Size, Align> {
     /// Creates a new [`Bump`].
+    ///
+    /// `Size` must not be zero-sized, and a chunk (including a small amount
+    /// of bookkeeping data) must not be too large for a [`Layout`]. These
+    /// conditions are checked at compile time:
+    ///
+    /// ```compile_fail
+    /// let bump = fixed_bump::Bump::<[u8; 0]>::new();
+    /// ```
     pub fn new() -> Self {
+        // Evaluating this constant turns invalid chunk layouts into
+        // compile-time errors.
+        let _ = ConstLayout::<Size, Align>::FULL;
         Self(GenericBump::new(ConstLayout(PhantomData)))
     }
 
-    /// Tries to allocate memory with a size and alignment matching `layout`.
-    ///
-    /// Returns a pointer to the memory on success, or [`None`] on failure.
-    /// The memory is valid until the [`Bump`] is dropped. Note that the
-    /// returned memory could be larger than [`layout.size()`].
-    ///
-    /// This method is similar to [`Allocator::allocate`], except it returns an
-    /// [`Option`] instead of a [`Result`].
-    ///
-    /// Allocation is guaranteed to succeed, assuming the global allocator
-    /// succeeds, if [`layout.size()`] is less than or equal to
-    /// [`mem::size_of::<Size>()`] and [`layout.align()`] is less than or equal
-    /// to [`mem::align_of::<Align>()`]. See [`Self::can_allocate`].
-    ///
-    /// [`layout.size()`]: Layout::size
-    /// [`layout.align()`]: Layout::align
-    /// [`Allocator::allocate`]: alloc::alloc::Allocator::allocate
-    pub fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
-        self.0.allocate(layout)
+    /// Creates a new [`Bump`] with `n` spare chunks already allocated (see
+    /// [`Self::reserve_chunks`]).
+    pub fn with_reserved_chunks(n: usize) -> Self {
+        let bump = Self::new();
+        bump.reserve_chunks(n);
+        bump
     }
 
-    /// Allocates a value of type `T`.
-    ///
-    /// The memory is initialized with `value` and a reference to the value is
-    /// returned. Note that the value's destructor will not be called
-    /// automatically.
+    /// Creates a new [`Bump`] that takes chunks from `pool` before
+    /// allocating them, and returns its chunks to `pool` when dropped.
     ///
     /// # Panics
     ///
-    /// Panics if this allocator cannot allocate memory matching
-    /// [`Layout::new::<T>()`] (see [`Self::can_allocate`]). Note that if the
-    /// global allocator fails, [`handle_alloc_error`] is called instead of
-    /// panicking.
-    ///
-    /// For an equivalent that doesn't panic or call [`handle_alloc_error`],
-    /// see [`Self::try_alloc_value`].
+    /// Panics if <code>pool.[layout()]</code> is not the layout of `Size`
+    /// with the alignment of `Align`.
     ///
-    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
-    #[allow(clippy::mut_from_ref)]
-    #[must_use]
-    pub fn alloc_value<T>(&self, value: T) -> &mut T {
-        self.0.alloc_value(value)
+    /// [layout()]: ChunkPool::layout
+    pub fn with_pool(pool: ChunkPool) -> Self {
+        Self::with_any_pool(Pool::Local(pool))
     }
 
-    /// Tries to allocate a value of type `T`.
+    /// Like [`Self::with_pool`], but uses a [`SyncChunkPool`], which can be
+    /// shared across threads.
     ///
-    /// If the allocation succeeds, the memory is initialized with `value` and
-    /// a reference to the value is returned. Note that the value's destructor
-    /// will not be called automatically.
-    ///
-    /// Allocation succeeds if and only if [`Self::allocate`] is able to
-    /// allocate memory matching [`Layout::new::<T>()`]. See [`Self::allocate`]
-    /// for details regarding the circumstances in which allocation can fail.
+    /// # Panics
     ///
-    /// # Errors
+    /// Panics if <code>pool.[layout()]</code> is not the layout of `Size`
+    /// with the alignment of `Align`.
     ///
-    /// If allocation fails, <code>[Err]\(value)</code> is returned.
-    #[allow(clippy::mut_from_ref)]
-    pub fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
-        self.0.try_alloc_value(value)
+    /// [layout()]: SyncChunkPool::layout
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+    pub fn with_sync_pool(pool: SyncChunkPool) -> Self {
+        Self::with_any_pool(Pool::Sync(pool))
     }
 
-    /// Returns whether this allocator can allocate memory matching `layout`.
-    ///
-    /// This is guaranteed to return true if [`layout.size()`] is less than or
-    /// equal to [`mem::size_of::<Size>()`] and [`layout.align()`] is less than
-    /// or equal to [`mem::align_of::<Align>()`]. It *may* return true if the
-    /// alignment is bigger, but never if the size is.
-    ///
-    /// [`layout.size()`]: Layout::size
-    /// [`layout.align()`]: Layout::align
-    pub fn can_allocate(&self, layout: Layout) -> bool {
-        self.0.can_allocate(layout)
+    /// Creates a new [`Bump`] whose chunks can have a total size of at most
+    /// `bytes` bytes. See [`Self::set_limit`].
+    pub fn with_limit(bytes: usize) -> Self {
+        let mut bump = Self::new();
+        bump.set_limit(Some(bytes));
+        bump
     }
-}
 
-impl<Size, Align> Default for Bump<Size, Align> {
-    fn default() -> Self {
-        Self::new()
+    /// Creates a new [`Bump`] that uses at most `n` chunks. See
+    /// [`Self::set_max_chunks`].
+    pub fn with_max_chunks(n: usize) -> Self {
+        let mut bump = Self::new();
+        bump.set_max_chunks(Some(n));
+        bump
+    }
+
+    /// Creates a new [`Bump`] that draws the size of each of its chunks from
+    /// `budget`, which can be shared with other allocators. See
+    /// [`Self::set_budget`].
+    #[cfg(target_has_atomic = "ptr")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "ptr")))]
+    pub fn with_budget(budget: Budget) -> Self {
+        let mut bump = Self::new();
+        bump.set_budget(Some(budget));
+        bump
+    }
+
+    fn with_any_pool(pool: Pool) -> Self {
+        let layout = ConstLayout(PhantomData);
+        assert!(
+            pool.layout() == layout.chunk_layout(),
+            "pool layout does not match the chunk layout",
+        );
+        Self(GenericBump::with_pool(layout, pool))
     }
 }
 
-#[cfg(any(feature = "allocator_api", feature = "allocator-fallback"))]
-#[cfg_attr(
-    feature = "doc_cfg",
-    doc(cfg(any(
-        feature = "allocator_api",
-        feature = "allocator-fallback",
-    )))
-)]
-// SAFETY: `Bump::allocate` (when not returning `None`) returns pointers to
-// valid memory that matches the provided `Layout`.
-//
-// `Bump` cannot be cloned, as it does not implement `Clone`. Moving it will
-// not invalidate any returned memory, as all returned memory is allocated on
-// the heap via the global allocator.
-unsafe impl<Size, Align> Allocator for Bump<Size, Align> {
-    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-        self.allocate(layout).ok_or(AllocError)
+impl<Size, Align, A: ChunkSource> Bump<Size, Align, A> {
+    /// Like [`Bump::new`], but allocates chunks from `source` instead of the
+    /// global allocator. Chunks are returned to `source` when the allocator
+    /// is dropped.
+    pub fn new_in(source: A) -> Self {
+        // See `Bump::new`.
+        let _ = ConstLayout::<Size, Align>::FULL;
+        Self(GenericBump::new_in(ConstLayout(PhantomData), source))
     }
+}
 
-    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
-        // No-op: `Bump` deallocates all its memory when dropped.
+bump_methods! {
+    impl[Size, Align, A: ChunkSource] Bump<Size, Align, A>,
+    chunk_size = "[`mem::size_of::<Size>()`]",
+    chunk_align = "[`mem::align_of::<Align>()`]",
+    example = [
+        "use fixed_bump::Bump;",
+        "let bump = Bump::<[u64; 4]>::new();",
+    ],
+}
+
+const_layout_methods! {
+    impl[Size, Align, A: ChunkSource] Bump<Size, Align, A>,
+    layout = ConstLayout<Size, Align>,
+    chunk_size = "[`mem::size_of::<Size>()`]",
+    chunk_align = "[`mem::align_of::<Align>()`]",
+    too_small = "Bump::<[u8; 4]>::new()",
+    example = "Bump::<[u64; 8]>",
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
+impl<Size, Align> Default for Bump<Size, Align> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
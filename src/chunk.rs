@@ -17,12 +17,47 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use alloc::alloc::Layout;
-use core::mem;
-use core::ptr::{NonNull, addr_of_mut};
+use super::error::ChunkLayoutError;
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
+use core::mem::{self, MaybeUninit};
+use core::ptr::{self, NonNull};
 
 struct ChunkHeader {
     prev: Option<Chunk>,
+    layout: Layout,
+}
+
+/// A [`ChunkHeader`] padded to the alignment of `Align`, like the header of a
+/// chunk whose layout has the alignment of `Align` (see
+/// [`Chunk::header_size`]).
+#[repr(C)]
+// The fields are never read; only the struct's layout matters.
+#[allow(dead_code)]
+struct PaddedHeader<Align> {
+    header: ChunkHeader,
+    align: [Align; 0],
+}
+
+/// Uninitialized memory large and aligned enough for a chunk whose layout has
+/// the size of `Size` and the alignment of `Align`, including its header.
+#[repr(C)]
+// The fields are never read; only the struct's layout matters.
+#[allow(dead_code)]
+pub struct ChunkMemory<Size, Align> {
+    header: MaybeUninit<PaddedHeader<Align>>,
+    storage: MaybeUninit<Size>,
+    align: [Align; 0],
+}
+
+impl<Size, Align> ChunkMemory<Size, Align> {
+    pub fn uninit() -> Self {
+        Self {
+            header: MaybeUninit::uninit(),
+            storage: MaybeUninit::uninit(),
+            align: [],
+        }
+    }
 }
 
 // Invariant: `self.0` always points to a valid, initialized, properly aligned
@@ -31,30 +66,70 @@ struct ChunkHeader {
 pub struct Chunk(NonNull<ChunkHeader>);
 
 impl Chunk {
-    pub fn new(layout: Layout, prev: Option<Self>) -> Option<Self> {
-        let layout = Self::full_layout(layout);
-        assert!(layout.size() > 0);
+    /// Allocates a new chunk from the global allocator. The chunk remembers
+    /// `layout`, which is later used to deallocate it (see
+    /// [`Self::requested_layout`]).
+    #[cfg(feature = "alloc")]
+    pub fn new(layout: Layout) -> Option<Self> {
+        Self::new_in(layout, &Global)
+    }
+
+    /// Like [`Self::new`], but allocates the chunk from `source`.
+    pub fn new_in<S: ChunkSource>(layout: Layout, source: &S) -> Option<Self> {
+        let full_layout = Self::full_layout(layout);
+        assert!(full_layout.size() > 0);
 
-        // SAFETY: We ensured `layout` has non-zero size above.
-        let ptr: NonNull<ChunkHeader> =
-            NonNull::new(unsafe { alloc::alloc::alloc(layout) })?.cast();
+        let ptr: NonNull<ChunkHeader> = source.allocate(full_layout)?.cast();
 
-        // SAFETY: `alloc::alloc::alloc` returns valid, properly aligned
-        // memory.
+        // SAFETY: `ChunkSource::allocate` returns valid memory that matches
+        // `full_layout`, which is properly aligned for `ChunkHeader`.
         unsafe {
-            addr_of_mut!((*ptr.as_ptr()).prev).write(prev);
+            ptr.as_ptr().write(ChunkHeader {
+                prev: None,
+                layout,
+            });
         }
         Some(Self(ptr))
     }
 
+    /// The layout that was passed to [`Self::new`].
+    pub fn requested_layout(&self) -> Layout {
+        // SAFETY: `self.0` is always initialized and properly aligned.
+        unsafe { (*self.0.as_ptr()).layout }
+    }
+
     /// The layout of the memory returned by [`Self::storage`], assuming
     /// `layout` was passed to [`Self::new`]. The size and alignment are
     /// guaranteed to be greater than or equal to the size and alignment of
     /// `layout`, respectively.
-    pub fn layout(layout: Layout) -> Layout {
-        let size = layout.size();
-        let align = layout.align().max(mem::align_of::<ChunkHeader>());
-        Layout::from_size_align(size, align).unwrap()
+    ///
+    /// This is a `const fn` so that layouts known at compile time can be
+    /// computed in constants.
+    pub const fn layout(layout: Layout) -> Layout {
+        match Layout::from_size_align(layout.size(), Self::align(layout)) {
+            Ok(layout) => layout,
+            Err(_) => panic!("chunk layout is too large"),
+        }
+    }
+
+    /// The alignment of [`Self::layout`] and [`Self::full_layout`].
+    const fn align(layout: Layout) -> usize {
+        let header = mem::align_of::<ChunkHeader>();
+        if layout.align() > header {
+            layout.align()
+        } else {
+            header
+        }
+    }
+
+    /// The offset of the storage from the start of the chunk: the size of
+    /// the header, padded to a multiple of [`Self::align`] so that the
+    /// storage is as aligned as the chunk itself.
+    const fn header_size(layout: Layout) -> usize {
+        let align = Self::align(layout);
+        // `align` is a power of two no greater than `isize::MAX + 1`, so this
+        // doesn't overflow.
+        (mem::size_of::<ChunkHeader>() + align - 1) & !(align - 1)
     }
 
     /// The layout of the entire block of memory allocated by [`Self::new`],
@@ -62,25 +137,69 @@ impl Chunk {
     /// useful mainly when calling [`handle_alloc_error`].
     ///
     /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
-    pub fn full_layout(layout: Layout) -> Layout {
-        let size =
-            layout.size().checked_add(mem::size_of::<ChunkHeader>()).unwrap();
-        let align = layout.align().max(mem::align_of::<ChunkHeader>());
-        Layout::from_size_align(size, align).unwrap()
+    pub const fn full_layout(layout: Layout) -> Layout {
+        match Self::try_full_layout(layout) {
+            Some(layout) => layout,
+            None => panic!("chunk layout is too large"),
+        }
+    }
+
+    /// Like [`Self::full_layout`], but returns `None` if the size of the
+    /// full layout would overflow.
+    const fn try_full_layout(layout: Layout) -> Option<Layout> {
+        let header = Self::header_size(layout);
+        let size = match layout.size().checked_add(header) {
+            Some(size) => size,
+            None => return None,
+        };
+        match Layout::from_size_align(size, Self::align(layout)) {
+            Ok(layout) => Some(layout),
+            Err(_) => None,
+        }
+    }
+
+    /// Checks that `layout` is suitable for chunks: its size must be
+    /// non-zero, and [`Self::full_layout`] must not overflow.
+    pub fn check_layout(layout: Layout) -> Result<(), ChunkLayoutError> {
+        if layout.size() == 0 {
+            return Err(ChunkLayoutError::ZeroSize);
+        }
+        match Self::try_full_layout(layout) {
+            Some(_) => Ok(()),
+            None => Err(ChunkLayoutError::Overflow),
+        }
     }
 
     /// Returns a pointer to the start of the storage. It is guaranteed to
     /// match [`Self::layout(cl)`], where `cl` is the layout that was passed to
     /// [`Self::new`]. Note that the memory could be uninitialized.
     pub fn storage(&self) -> NonNull<u8> {
-        // SAFETY: `self.0` points to a valid `ChunkHeader`, so adding 1 must
-        // result in a pointer within or one byte past the end of the same
-        // allocated object.
-        let end = unsafe { self.0.as_ptr().add(1) };
+        let offset = Self::header_size(self.requested_layout());
+        // SAFETY: The chunk was allocated with [`Self::full_layout`], whose
+        // size is at least `offset`, so this results in a pointer within or
+        // one byte past the end of the same allocated object. The chunk is
+        // aligned to `Self::align`, and `offset` is a multiple of it.
+        let storage = unsafe { self.0.as_ptr().cast::<u8>().add(offset) };
 
-        // SAFETY: `self.0` is non-null and points to a valid object, so `end`
-        // must also be non-null.
-        unsafe { NonNull::new_unchecked(end) }.cast()
+        // SAFETY: `self.0` is non-null and points to a valid object, so
+        // `storage` must also be non-null.
+        unsafe { NonNull::new_unchecked(storage) }
+    }
+
+    /// Writes to every `page_size`-byte page of the storage, so that the
+    /// operating system maps them now rather than on first use.
+    pub fn prefault(&self, page_size: usize) {
+        let size = Self::layout(self.requested_layout()).size();
+        let storage = self.storage().as_ptr();
+        let last = size.checked_sub(1).into_iter();
+        for offset in (0..size).step_by(page_size).chain(last) {
+            // SAFETY: `offset` is less than the size of the storage. The
+            // storage isn't in use yet, so overwriting it is fine; a volatile
+            // write ensures the write actually happens.
+            unsafe {
+                ptr::write_volatile(storage.add(offset), 0);
+            }
+        }
     }
 
     pub fn take_prev(&mut self) -> Option<Self> {
@@ -88,17 +207,97 @@ impl Chunk {
         unsafe { &mut (*self.0.as_ptr()).prev }.take()
     }
 
+    pub fn set_prev(&mut self, prev: Option<Self>) {
+        // SAFETY: `self.0` is always initialized and properly aligned.
+        unsafe {
+            (*self.0.as_ptr()).prev = prev;
+        }
+    }
+
+    /// # Safety
+    ///
+    /// * The memory returned by [`Self::storage`] must no longer be in use.
+    /// * The chunk must have been allocated by [`Self::new`].
+    pub unsafe fn drop(self) {
+        // SAFETY: Checked by caller.
+        unsafe {
+            self.drop_in(&Global);
+        }
+    }
+
     /// # Safety
     ///
-    /// `layout` must be equal to the layout passed to [`Self::new`].
-    pub unsafe fn drop(self, layout: Layout) {
-        // SAFETY: `self.0` is always allocated by the global allocator. Caller
-        // ensures `layout` is correct.
+    /// * The memory returned by [`Self::storage`] must no longer be in use.
+    /// * The chunk must have been allocated by [`Self::new_in`] with
+    ///   `source`.
+    pub unsafe fn drop_in<S: ChunkSource>(self, source: &S) {
+        let layout = Self::full_layout(self.requested_layout());
+        // SAFETY: The caller guarantees `self.0` was allocated by `source`
+        // with the full layout corresponding to the stored layout.
         unsafe {
-            alloc::alloc::dealloc(
-                self.0.as_ptr().cast(),
-                Self::full_layout(layout),
-            );
+            source.deallocate(self.0.cast(), layout);
+        }
+    }
+}
+
+/// A singly linked list of chunks that are not in use. Chunks left in the
+/// list when it is dropped are deallocated with the global allocator, so
+/// chunks from other sources must be removed first.
+#[derive(Default)]
+pub struct ChunkList {
+    head: Option<Chunk>,
+    len: usize,
+}
+
+impl ChunkList {
+    pub const fn new() -> Self {
+        Self {
+            head: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The layout of the first chunk in the list, if any.
+    pub fn first_layout(&self) -> Option<Layout> {
+        self.head.as_ref().map(Chunk::requested_layout)
+    }
+
+    /// # Safety
+    ///
+    /// * The memory returned by [`Chunk::storage`] must no longer be in use.
+    /// * If the list could be dropped with `chunk` still in it, `chunk` must
+    ///   have been allocated by [`Chunk::new`].
+    pub unsafe fn push(&mut self, mut chunk: Chunk) {
+        chunk.set_prev(self.head.take());
+        self.head = Some(chunk);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<Chunk> {
+        let mut chunk = self.head.take()?;
+        self.head = chunk.take_prev();
+        self.len -= 1;
+        Some(chunk)
+    }
+}
+
+// SAFETY: The chunks in a `ChunkList` are not in use and are owned
+// exclusively by the list, so they can be deallocated from any thread.
+unsafe impl Send for ChunkList {}
+
+impl Drop for ChunkList {
+    fn drop(&mut self) {
+        while let Some(chunk) = self.pop() {
+            // SAFETY: Chunks in the list are not in use, and chunks that
+            // remain in the list when it is dropped were allocated by
+            // `Chunk::new`, as required by `Self::push`.
+            unsafe {
+                chunk.drop();
+            }
         }
     }
 }
@@ -0,0 +1,243 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use super::budget::Budget;
+#[cfg(feature = "alloc")]
+use super::chunk::Chunk;
+#[cfg(feature = "alloc")]
+use super::error::ChunkLayoutError;
+use super::generic::{ChunkLayout, GenericBump};
+use super::methods::bump_methods;
+#[cfg(feature = "std")]
+use super::pool::SyncChunkPool;
+#[cfg(feature = "alloc")]
+use super::pool::{ChunkPool, Pool};
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
+
+/// Like [`DynamicBump`], but takes its chunk layout from a user-provided
+/// [`ChunkLayout`].
+///
+/// The layout source is stored in the allocator and can be accessed with
+/// [`Self::source`]. The allocator asks it for the layout each time it needs
+/// one, so a source backed by runtime configuration takes effect for the next
+/// chunk without calling [`Self::set_source`]. Otherwise, this type behaves
+/// identically to [`DynamicBump`]; in particular, chunks are allocated with
+/// the global allocator unless a different [`ChunkSource`] is given as the
+/// type parameter `A` (see [`Self::new_in`]).
+///
+/// ```
+/// use fixed_bump::{ChunkLayout, CustomBump};
+/// use std::alloc::Layout;
+///
+/// struct PageChunks;
+///
+/// impl ChunkLayout for PageChunks {
+///     fn chunk_layout(&self) -> Layout {
+///         Layout::from_size_align(4096, 4096).unwrap()
+///     }
+/// }
+///
+/// let bump = CustomBump::new(PageChunks);
+/// let item = bump.alloc_value(123_u64);
+/// assert_eq!(*item, 123);
+/// assert_eq!(bump.layout().align(), 4096);
+/// ```
+///
+/// [`DynamicBump`]: crate::DynamicBump
+pub struct CustomBump<L: ChunkLayout, A: ChunkSource = Global>(
+    GenericBump<L, A>,
+);
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
+impl<L: ChunkLayout> CustomBump<L> {
+    /// Creates a new [`CustomBump`]. The size and alignment of the chunks
+    /// allocated internally by the allocator are given by
+    /// <code>source.[chunk_layout()]</code>.
+    ///
+    /// [chunk_layout()]: ChunkLayout::chunk_layout
+    pub fn new(source: L) -> Self {
+        Self(GenericBump::new(source))
+    }
+
+    /// Like [`Self::new`], but checks the layout provided by `source` first.
+    /// See [`DynamicBump::try_new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size of the layout is zero, or if the size of
+    /// a chunk, including a small amount of bookkeeping data, would overflow.
+    ///
+    /// [`DynamicBump::try_new`]: crate::DynamicBump::try_new
+    pub fn try_new(source: L) -> Result<Self, ChunkLayoutError> {
+        let layout = source.chunk_layout();
+        Chunk::check_layout(layout)?;
+        Ok(Self::new(source))
+    }
+
+    /// Creates a new [`CustomBump`] with `n` spare chunks already allocated
+    /// (see [`Self::reserve_chunks`]). `source` provides the layout of the
+    /// chunks, as in [`Self::new`].
+    pub fn with_reserved_chunks(source: L, n: usize) -> Self {
+        let bump = Self::new(source);
+        bump.reserve_chunks(n);
+        bump
+    }
+
+    /// Creates a new [`CustomBump`] that takes chunks from `pool` before
+    /// allocating them, and returns its chunks to `pool` when dropped.
+    /// `source` provides the layout of the chunks, as in [`Self::new`].
+    ///
+    /// If the layout provided by the source later changes, chunks with the
+    /// new layout bypass the pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if <code>pool.[layout()]</code> is not the layout provided by
+    /// `source`.
+    ///
+    /// [layout()]: ChunkPool::layout
+    pub fn with_pool(source: L, pool: ChunkPool) -> Self {
+        Self::with_any_pool(source, Pool::Local(pool))
+    }
+
+    /// Like [`Self::with_pool`], but uses a [`SyncChunkPool`], which can be
+    /// shared across threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if <code>pool.[layout()]</code> is not the layout provided by
+    /// `source`.
+    ///
+    /// [layout()]: SyncChunkPool::layout
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+    pub fn with_sync_pool(source: L, pool: SyncChunkPool) -> Self {
+        Self::with_any_pool(source, Pool::Sync(pool))
+    }
+
+    /// Creates a new [`CustomBump`] whose chunks can have a total size of at
+    /// most `bytes` bytes. `source` provides the layout of the chunks, as in
+    /// [`Self::new`]. See [`Self::set_limit`].
+    pub fn with_limit(source: L, bytes: usize) -> Self {
+        let mut bump = Self::new(source);
+        bump.set_limit(Some(bytes));
+        bump
+    }
+
+    /// Creates a new [`CustomBump`] that uses at most `n` chunks. `source`
+    /// provides the layout of the chunks, as in [`Self::new`]. See
+    /// [`Self::set_max_chunks`].
+    pub fn with_max_chunks(source: L, n: usize) -> Self {
+        let mut bump = Self::new(source);
+        bump.set_max_chunks(Some(n));
+        bump
+    }
+
+    /// Creates a new [`CustomBump`] that draws the size of each of its chunks
+    /// from `budget`, which can be shared with other allocators. `source`
+    /// provides the layout of the chunks, as in [`Self::new`]. See
+    /// [`Self::set_budget`].
+    #[cfg(target_has_atomic = "ptr")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "ptr")))]
+    pub fn with_budget(source: L, budget: Budget) -> Self {
+        let mut bump = Self::new(source);
+        bump.set_budget(Some(budget));
+        bump
+    }
+
+    fn with_any_pool(source: L, pool: Pool) -> Self {
+        let layout = source.chunk_layout();
+        assert!(
+            pool.layout() == layout,
+            "pool layout does not match the chunk layout",
+        );
+        Self(GenericBump::with_pool(source, pool))
+    }
+}
+
+impl<L: ChunkLayout, A: ChunkSource> CustomBump<L, A> {
+    /// Like [`CustomBump::new`], but allocates chunks from `chunk_source`
+    /// instead of the global allocator. Chunks are returned to
+    /// `chunk_source` when the allocator is dropped.
+    pub fn new_in(source: L, chunk_source: A) -> Self {
+        Self(GenericBump::new_in(source, chunk_source))
+    }
+
+    /// The layout source passed to [`Self::new`] or [`Self::new_in`], or the
+    /// one most recently passed to [`Self::set_source`].
+    pub fn source(&self) -> &L {
+        self.0.layout_source()
+    }
+
+    /// The layout used for new chunks, as currently returned by the layout
+    /// source.
+    pub fn layout(&self) -> Layout {
+        self.0.layout()
+    }
+
+    /// Replaces the layout source and changes the size and alignment of
+    /// chunks allocated from now on to the layout it provides. The previous
+    /// source is returned.
+    ///
+    /// Chunks that have already been allocated keep their original layout
+    /// and are deallocated accordingly, so memory previously returned by this
+    /// allocator remains valid. Allocation continues in the current chunk
+    /// until it is full, unless its alignment is smaller than that of the new
+    /// layout, in which case the next allocation starts a new chunk.
+    pub fn set_source(&mut self, source: L) -> L {
+        self.0.set_layout(source)
+    }
+
+    /// The number of values of type `T` that fit in each new chunk, taking
+    /// into account the padding needed for alignment. This is [`usize::MAX`]
+    /// if `T` is zero-sized, and zero if `T` is more aligned than the chunks.
+    pub fn items_per_chunk<T>(&self) -> usize {
+        self.0.items_per_chunk::<T>()
+    }
+}
+
+bump_methods! {
+    impl[L: ChunkLayout, A: ChunkSource] CustomBump<L, A>,
+    chunk_size = "the size of [`Self::layout`]",
+    chunk_align = "the alignment of [`Self::layout`]",
+    set_layout = set_source,
+    example = [
+        "use fixed_bump::CustomBump;",
+        "# use std::alloc::Layout;",
+        "let bump = CustomBump::new(Layout::new::<[u64; 4]>());",
+    ],
+}
+
+#[cfg(any(doc, doctest))]
+/// [`CustomBump`] cannot implement [`Clone`], as this would make it unsound
+/// to implement [`Allocator`](alloc::alloc::Allocator).
+///
+/// ```
+/// use fixed_bump::CustomBump;
+/// struct Test<T = CustomBump<std::alloc::Layout>>(T);
+/// ```
+///
+/// ```compile_fail
+/// use fixed_bump::CustomBump;
+/// struct Test<T: Clone = CustomBump<std::alloc::Layout>>(T);
+/// ```
+mod custom_bump_does_not_impl_clone {}
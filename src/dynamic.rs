@@ -17,15 +17,24 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use super::generic::{GenericBump, IntoLayout};
-#[cfg(any(feature = "allocator_api", feature = "allocator-fallback"))]
-use super::{AllocError, Allocator};
-use alloc::alloc::Layout;
-use core::ptr::NonNull;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use super::budget::Budget;
+use super::chunk::Chunk;
+use super::error::ChunkLayoutError;
+use super::generic::{ChunkLayout, GenericBump};
+use super::methods::bump_methods;
+#[cfg(feature = "std")]
+use super::pool::SyncChunkPool;
+#[cfg(feature = "alloc")]
+use super::pool::{ChunkPool, Pool};
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
 
-// SAFETY: Trivially, `<Layout as Into<Layout>>::into` cannot call any methods
-// of any [`GenericBump`] as it is a no-op.
-unsafe impl IntoLayout for Layout {}
+impl ChunkLayout for Layout {
+    fn chunk_layout(&self) -> Layout {
+        *self
+    }
+}
 
 /// Like [`Bump`], but uses chunk size and alignment values provided at runtime
 /// rather than compile time.
@@ -33,9 +42,15 @@ unsafe impl IntoLayout for Layout {}
 /// Instead of passing `Size` and `Align` type parameters, [`Self::new`]
 /// accepts a [`Layout`]. Otherwise, this type behaves identically to [`Bump`].
 ///
+/// Like [`Bump`], chunks are allocated with the global allocator unless a
+/// different [`ChunkSource`] is given as the type parameter `A` (see
+/// [`Self::new_in`]).
+///
 /// [`Bump`]: crate::Bump
-pub struct DynamicBump(GenericBump<Layout>);
+pub struct DynamicBump<A: ChunkSource = Global>(GenericBump<Layout, A>);
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
 impl DynamicBump {
     /// Creates a new [`DynamicBump`]. `layout` specifies the size and
     /// alignment of the chunks allocated internally by the allocator.
@@ -43,116 +58,164 @@ impl DynamicBump {
         Self(GenericBump::new(layout))
     }
 
-    /// The layout passed to [`Self::new`].
-    pub fn layout(&self) -> Layout {
-        self.0.layout()
-    }
-
-    /// Tries to allocate memory with a size and alignment matching `layout`.
+    /// Like [`Self::new`], but checks `layout` first.
     ///
-    /// Returns a pointer to the memory on success, or [`None`] on failure.
-    /// The memory is valid until the [`DynamicBump`] is dropped. Note that the
-    /// returned memory could be larger than [`layout.size()`].
+    /// [`Self::new`] accepts any layout, but chunks with a size of zero can't
+    /// hold anything other than zero-sized values, and a layout that is too
+    /// large causes a panic when the first chunk is allocated. This method
+    /// rejects such layouts up front. See also [`ChunkSpec`] for layouts that
+    /// come from configuration files.
     ///
-    /// This method is similar to [`Allocator::allocate`], except it returns an
-    /// [`Option`] instead of a [`Result`].
+    /// # Errors
     ///
-    /// Allocation is guaranteed to succeed, assuming the global allocator
-    /// succeeds, if [`layout.size()`] is less than or equal to
-    /// <code>[self.layout()].[size()]</code> and [`layout.align()`] is less
-    /// than or equal to <code>[self.layout()].[align()]</code>. See
-    /// [`Self::can_allocate`].
+    /// Returns an error if [`layout.size()`] is zero, or if the size of
+    /// a chunk, including a small amount of bookkeeping data, would overflow.
     ///
     /// [`layout.size()`]: Layout::size
-    /// [`layout.align()`]: Layout::align
-    /// [`Allocator::allocate`]: alloc::alloc::Allocator::allocate
-    /// [self.layout()]: Self::layout
-    /// [size()]: Layout::size
-    /// [align()]: Layout::align
-    pub fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
-        self.0.allocate(layout)
+    /// [`ChunkSpec`]: crate::ChunkSpec
+    pub fn try_new(layout: Layout) -> Result<Self, ChunkLayoutError> {
+        Chunk::check_layout(layout)?;
+        Ok(Self::new(layout))
     }
 
-    /// Allocates a value of type `T`.
+    /// Creates a new [`DynamicBump`] whose chunks are large and aligned
+    /// enough to hold `n` values of type `T`; that is, whose chunk layout is
+    /// the layout of `[T; n]`.
     ///
-    /// The memory is initialized with `value` and a reference to the value is
-    /// returned. Note that the value's destructor will not be called
-    /// automatically.
+    /// ```
+    /// use fixed_bump::DynamicBump;
+    /// let bump = DynamicBump::for_items::<u64>(32);
+    /// assert_eq!(bump.items_per_chunk::<u64>(), 32);
+    /// ```
     ///
     /// # Panics
     ///
-    /// Panics if this allocator cannot allocate memory matching
-    /// [`Layout::new::<T>()`] (see [`Self::can_allocate`]). Note that if the
-    /// global allocator fails, [`handle_alloc_error`] is called instead of
-    /// panicking.
-    ///
-    /// For an equivalent that doesn't panic or call [`handle_alloc_error`],
-    /// see [`Self::try_alloc_value`].
-    ///
-    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
-    #[allow(clippy::mut_from_ref)]
-    #[must_use]
-    pub fn alloc_value<T>(&self, value: T) -> &mut T {
-        self.0.alloc_value(value)
+    /// Panics if the size of `n` values of type `T` would overflow.
+    pub fn for_items<T>(n: usize) -> Self {
+        Self::new(Layout::array::<T>(n).expect("chunk layout is too large"))
     }
 
-    /// Tries to allocate a value of type `T`.
-    ///
-    /// If the allocation succeeds, the memory is initialized with `value` and
-    /// a reference to the value is returned. Note that the value's destructor
-    /// will not be called automatically.
-    ///
-    /// Allocation succeeds if and only if [`Self::allocate`] is able to
-    /// allocate memory matching [`Layout::new::<T>()`]. See [`Self::allocate`]
-    /// for details regarding the circumstances in which allocation can fail.
-    ///
-    /// # Errors
+    /// Creates a new [`DynamicBump`] with `n` spare chunks already allocated
+    /// (see [`Self::reserve_chunks`]). `layout` specifies the size and
+    /// alignment of the chunks, as in [`Self::new`].
+    pub fn with_reserved_chunks(layout: Layout, n: usize) -> Self {
+        let bump = Self::new(layout);
+        bump.reserve_chunks(n);
+        bump
+    }
+
+    /// Creates a new [`DynamicBump`] that uses chunks with the layout of
+    /// `pool`. Chunks are taken from `pool` before being allocated, and are
+    /// returned to `pool` when the allocator is dropped.
     ///
-    /// If allocation fails, <code>[Err]\(value)</code> is returned.
-    #[allow(clippy::mut_from_ref)]
-    pub fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
-        self.0.try_alloc_value(value)
+    /// If the chunk layout is later changed with [`Self::set_chunk_layout`],
+    /// chunks with the new layout bypass the pool.
+    pub fn with_pool(pool: ChunkPool) -> Self {
+        let layout = pool.layout();
+        Self(GenericBump::with_pool(layout, Pool::Local(pool)))
+    }
+
+    /// Like [`Self::with_pool`], but uses a [`SyncChunkPool`], which can be
+    /// shared across threads.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+    pub fn with_sync_pool(pool: SyncChunkPool) -> Self {
+        let layout = pool.layout();
+        Self(GenericBump::with_pool(layout, Pool::Sync(pool)))
     }
 
-    /// Returns whether this allocator can allocate memory matching `layout`.
+    /// Creates a new [`DynamicBump`] whose chunks can have a total size of at
+    /// most `bytes` bytes. `layout` specifies the size and alignment of the
+    /// chunks, as in [`Self::new`]. See [`Self::set_limit`].
+    pub fn with_limit(layout: Layout, bytes: usize) -> Self {
+        let mut bump = Self::new(layout);
+        bump.set_limit(Some(bytes));
+        bump
+    }
+
+    /// Creates a new [`DynamicBump`] that uses at most `n` chunks. `layout`
+    /// specifies the size and alignment of the chunks, as in [`Self::new`].
+    /// See [`Self::set_max_chunks`].
+    pub fn with_max_chunks(layout: Layout, n: usize) -> Self {
+        let mut bump = Self::new(layout);
+        bump.set_max_chunks(Some(n));
+        bump
+    }
+
+    /// Creates a new [`DynamicBump`] that draws the size of each of its chunks
+    /// from `budget`, which can be shared with other allocators. `layout`
+    /// specifies the size and alignment of the chunks, as in [`Self::new`].
+    /// See [`Self::set_budget`].
+    #[cfg(target_has_atomic = "ptr")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "ptr")))]
+    pub fn with_budget(layout: Layout, budget: Budget) -> Self {
+        let mut bump = Self::new(layout);
+        bump.set_budget(Some(budget));
+        bump
+    }
+}
+
+impl<A: ChunkSource> DynamicBump<A> {
+    /// Like [`DynamicBump::new`], but allocates chunks from `source` instead
+    /// of the global allocator. Chunks are returned to `source` when the
+    /// allocator is dropped.
+    pub fn new_in(layout: Layout, source: A) -> Self {
+        Self(GenericBump::new_in(layout, source))
+    }
+
+    /// Like [`Self::new_in`], but checks `layout` first. See
+    /// [`DynamicBump::try_new`].
+    ///
+    /// # Errors
     ///
-    /// This is guaranteed to return true if [`layout.size()`] is less than or
-    /// equal to <code>[self.layout()].[size()]</code> and [`layout.align()`]
-    /// is less than or equal to <code>[self.layout()].[align()]</code>. It
-    /// *may* return true if the alignment is bigger, but never if the size is.
+    /// Returns an error if [`layout.size()`] is zero, or if the size of
+    /// a chunk, including a small amount of bookkeeping data, would overflow.
     ///
     /// [`layout.size()`]: Layout::size
-    /// [`layout.align()`]: Layout::align
-    /// [self.layout()]: Self::layout
-    /// [size()]: Layout::size
-    /// [align()]: Layout::align
-    pub fn can_allocate(&self, layout: Layout) -> bool {
-        self.0.can_allocate(layout)
+    pub fn try_new_in(
+        layout: Layout,
+        source: A,
+    ) -> Result<Self, ChunkLayoutError> {
+        Chunk::check_layout(layout)?;
+        Ok(Self::new_in(layout, source))
     }
-}
 
-#[cfg(any(feature = "allocator_api", feature = "allocator-fallback"))]
-#[cfg_attr(
-    feature = "doc_cfg",
-    doc(cfg(any(
-        feature = "allocator_api",
-        feature = "allocator-fallback",
-    )))
-)]
-// SAFETY: `DynamicBump::allocate` (when not returning `None`) returns pointers
-// to valid memory that matches the provided `Layout`.
-//
-// `DynamicBump` cannot be cloned, as it does not implement `Clone`. Moving it
-// will not invalidate any returned memory, as all returned memory is allocated
-// on the heap via the global allocator.
-unsafe impl Allocator for DynamicBump {
-    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-        self.allocate(layout).ok_or(AllocError)
+    /// The layout used for new chunks: either the layout passed to
+    /// [`Self::new`], or the one most recently passed to
+    /// [`Self::set_chunk_layout`].
+    pub fn layout(&self) -> Layout {
+        self.0.layout()
     }
 
-    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
-        // No-op: `DynamicBump` deallocates all its memory when dropped.
+    /// Changes the size and alignment of chunks allocated from now on.
+    ///
+    /// Chunks that have already been allocated keep their original layout
+    /// and are deallocated accordingly, so memory previously returned by this
+    /// allocator remains valid. Allocation continues in the current chunk
+    /// until it is full, unless its alignment is smaller than that of
+    /// `layout`, in which case the next allocation starts a new chunk.
+    pub fn set_chunk_layout(&mut self, layout: Layout) {
+        self.0.set_layout(layout);
     }
+
+    /// The number of values of type `T` that fit in each new chunk, taking
+    /// into account the padding needed for alignment. This is [`usize::MAX`]
+    /// if `T` is zero-sized, and zero if `T` is more aligned than the chunks.
+    pub fn items_per_chunk<T>(&self) -> usize {
+        self.0.items_per_chunk::<T>()
+    }
+}
+
+bump_methods! {
+    impl[A: ChunkSource] DynamicBump<A>,
+    chunk_size = "the size of [`Self::layout`]",
+    chunk_align = "the alignment of [`Self::layout`]",
+    set_layout = set_chunk_layout,
+    example = [
+        "use fixed_bump::DynamicBump;",
+        "# use std::alloc::Layout;",
+        "let bump = DynamicBump::new(Layout::new::<[u64; 4]>());",
+    ],
 }
 
 #[cfg(any(doc, doctest))]
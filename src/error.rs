@@ -0,0 +1,211 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use core::alloc::Layout;
+use core::fmt::{self, Display};
+
+/// The reason an allocation failed.
+///
+/// Returned by methods like [`Bump::try_allocate`]. Each variant holds the
+/// layout of the requested allocation (`requested`) and the layout of the
+/// allocator's chunks (`chunk`).
+///
+/// [`Bump::try_allocate`]: crate::Bump::try_allocate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AllocFailure {
+    /// The requested size is larger than the size of a chunk.
+    TooLarge {
+        requested: Layout,
+        chunk: Layout,
+    },
+    /// The requested alignment is larger than the alignment of a chunk.
+    OverAligned {
+        requested: Layout,
+        chunk: Layout,
+    },
+    /// A new chunk was needed, but the global allocator failed to allocate
+    /// it.
+    OutOfMemory {
+        requested: Layout,
+        chunk: Layout,
+    },
+    /// The allocator's chunks have a size of zero, so nothing other than
+    /// zero-sized values can be allocated.
+    ZeroSizedChunk {
+        requested: Layout,
+        chunk: Layout,
+    },
+    /// The allocator is sealed (see [`Bump::seal`]).
+    ///
+    /// [`Bump::seal`]: crate::Bump::seal
+    Sealed {
+        requested: Layout,
+        chunk: Layout,
+    },
+    /// A new chunk was needed, but obtaining it would have exceeded a memory
+    /// limit (see [`Bump::with_limit`]).
+    ///
+    /// [`Bump::with_limit`]: crate::Bump::with_limit
+    BudgetExceeded {
+        requested: Layout,
+        chunk: Layout,
+    },
+    /// The allocation didn't fit in the chunk used by the current allocation
+    /// group (see [`Bump::alloc_group`]).
+    ///
+    /// [`Bump::alloc_group`]: crate::Bump::alloc_group
+    GroupFull {
+        requested: Layout,
+        chunk: Layout,
+    },
+}
+
+impl AllocFailure {
+    fn layouts(&self) -> (Layout, Layout) {
+        match *self {
+            Self::TooLarge {
+                requested,
+                chunk,
+            }
+            | Self::OverAligned {
+                requested,
+                chunk,
+            }
+            | Self::OutOfMemory {
+                requested,
+                chunk,
+            }
+            | Self::ZeroSizedChunk {
+                requested,
+                chunk,
+            }
+            | Self::Sealed {
+                requested,
+                chunk,
+            }
+            | Self::BudgetExceeded {
+                requested,
+                chunk,
+            }
+            | Self::GroupFull {
+                requested,
+                chunk,
+            } => (requested, chunk),
+        }
+    }
+
+    /// The layout of the allocation that failed.
+    pub fn requested(&self) -> Layout {
+        self.layouts().0
+    }
+
+    /// The layout of the allocator's chunks at the time of the failure.
+    pub fn chunk_layout(&self) -> Layout {
+        self.layouts().1
+    }
+}
+
+impl Display for AllocFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (requested, chunk) = self.layouts();
+        match self {
+            Self::TooLarge {
+                ..
+            } => write!(
+                f,
+                "allocation of {} bytes is larger than the chunk size of {} \
+                 bytes",
+                requested.size(),
+                chunk.size(),
+            ),
+            Self::OverAligned {
+                ..
+            } => write!(
+                f,
+                "allocation alignment of {} is larger than the chunk \
+                 alignment of {}",
+                requested.align(),
+                chunk.align(),
+            ),
+            Self::OutOfMemory {
+                ..
+            } => write!(
+                f,
+                "global allocator failed to allocate a chunk of {} bytes",
+                chunk.size(),
+            ),
+            Self::ZeroSizedChunk {
+                ..
+            } => write!(
+                f,
+                "cannot allocate {} bytes in zero-sized chunks",
+                requested.size(),
+            ),
+            Self::Sealed {
+                ..
+            } => f.write_str("allocator is sealed"),
+            Self::BudgetExceeded {
+                ..
+            } => f.write_str("memory budget exceeded"),
+            Self::GroupFull {
+                ..
+            } => {
+                f.write_str("allocation group does not fit in a single chunk")
+            }
+        }
+    }
+}
+
+#[cfg(has_core_error)]
+impl core::error::Error for AllocFailure {}
+
+#[cfg(all(feature = "std", not(has_core_error)))]
+impl std::error::Error for AllocFailure {}
+
+/// An error indicating that a layout cannot be used for chunks.
+///
+/// Returned by constructors like [`DynamicBump::try_new`].
+///
+/// [`DynamicBump::try_new`]: crate::DynamicBump::try_new
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChunkLayoutError {
+    /// The chunk size is zero.
+    ZeroSize,
+    /// The chunk size, plus the space needed for the small amount of
+    /// bookkeeping data stored alongside each chunk, overflows [`isize`] once
+    /// rounded up to the chunk alignment.
+    Overflow,
+}
+
+impl Display for ChunkLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ZeroSize => "chunk size is zero",
+            Self::Overflow => "chunk size is too large",
+        })
+    }
+}
+
+#[cfg(has_core_error)]
+impl core::error::Error for ChunkLayoutError {}
+
+#[cfg(all(feature = "std", not(has_core_error)))]
+impl std::error::Error for ChunkLayoutError {}
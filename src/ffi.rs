@@ -0,0 +1,250 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A C API for [`DynamicBump`], available with the crate feature `ffi`.
+//!
+//! The functions in this module are exported with unmangled names, so that C
+//! and C++ code can use them through the header `include/fixed_bump.h`, which
+//! is generated from this module with cbindgen. The `fixed-bump-ffi` crate in
+//! the `ffi` directory of the repository builds them as a static and a shared
+//! library to link against:
+//!
+//! ```text
+//! cargo build --release --manifest-path ffi/Cargo.toml
+//! ```
+//!
+//! An allocator is created with [`fixed_bump_new`] and destroyed with
+//! [`fixed_bump_free`]. Allocators are not thread-safe: each one must be used
+//! by only one thread at a time.
+
+use super::DynamicBump;
+use super::chunk::Chunk;
+use super::error::AllocFailure;
+use super::pool::ChunkPool;
+use alloc::boxed::Box;
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::ffi::c_void;
+use core::ptr;
+
+/// The result of the most recent allocation; see [`fixed_bump_last_error`].
+///
+/// In C, this is `fixed_bump_status`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The allocation succeeded.
+    Ok = 0,
+    /// The requested size or alignment is larger than that of a chunk, so
+    /// the allocation can never succeed.
+    TooLarge = 1,
+    /// A new chunk was needed, but the global allocator failed to allocate
+    /// it.
+    OutOfMemory = 2,
+    /// The requested size and alignment don't form a valid layout: the
+    /// alignment isn't a power of two, or the size is too large.
+    InvalidLayout = 3,
+    /// The allocation failed for another reason, such as the allocator being
+    /// sealed or a memory limit being reached.
+    Other = 4,
+}
+
+impl From<AllocFailure> for Status {
+    fn from(failure: AllocFailure) -> Self {
+        match failure {
+            AllocFailure::TooLarge {
+                ..
+            }
+            | AllocFailure::OverAligned {
+                ..
+            }
+            | AllocFailure::ZeroSizedChunk {
+                ..
+            } => Self::TooLarge,
+            AllocFailure::OutOfMemory {
+                ..
+            } => Self::OutOfMemory,
+            AllocFailure::Sealed {
+                ..
+            }
+            | AllocFailure::GroupFull {
+                ..
+            }
+            | AllocFailure::BudgetExceeded {
+                ..
+            } => Self::Other,
+        }
+    }
+}
+
+/// Statistics about an allocator; see [`fixed_bump_get_stats`].
+///
+/// In C, this is `fixed_bump_stats`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The size of each chunk.
+    pub chunk_size: usize,
+    /// The alignment of each chunk.
+    pub chunk_align: usize,
+    /// The total size of the chunks in use since the allocator was created
+    /// or last reset, including the bookkeeping data stored alongside each
+    /// chunk.
+    pub chunk_bytes: usize,
+    /// The number of chunks kept for reuse after a reset.
+    pub free_chunks: usize,
+    /// The number of bytes left in the current chunk.
+    pub remaining: usize,
+}
+
+/// An allocator created by [`fixed_bump_new`].
+///
+/// In C, this is the opaque type `fixed_bump`.
+pub struct Handle {
+    bump: DynamicBump,
+    pool: ChunkPool,
+    status: Cell<Status>,
+}
+
+/// Creates a new allocator whose chunks have the given size and alignment.
+///
+/// Returns a null pointer if `size` is zero, `align` isn't a power of two, or
+/// the chunk layout is too large.
+#[no_mangle]
+pub extern "C" fn fixed_bump_new(size: usize, align: usize) -> *mut Handle {
+    let layout = match Layout::from_size_align(size, align) {
+        Ok(layout) => layout,
+        Err(_) => return ptr::null_mut(),
+    };
+    if Chunk::check_layout(layout).is_err() {
+        return ptr::null_mut();
+    }
+    // Chunks are kept in a pool, so that `fixed_bump_reset` can reuse them.
+    let pool = ChunkPool::new(layout);
+    Box::into_raw(Box::new(Handle {
+        bump: DynamicBump::with_pool(pool.clone()),
+        pool,
+        status: Cell::new(Status::Ok),
+    }))
+}
+
+/// Allocates memory with the given size and alignment.
+///
+/// Returns a pointer to the memory, which is valid until the allocator is
+/// reset or freed, or a null pointer on failure. Use [`fixed_bump_last_error`]
+/// to find out why an allocation failed.
+///
+/// # Safety
+///
+/// `bump` must have been returned by [`fixed_bump_new`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn fixed_bump_alloc(
+    bump: *mut Handle,
+    size: usize,
+    align: usize,
+) -> *mut c_void {
+    // SAFETY: Checked by caller.
+    let handle = unsafe { &*bump };
+    let result = Layout::from_size_align(size, align)
+        .map_err(|_| Status::InvalidLayout)
+        .and_then(|layout| {
+            handle.bump.try_allocate(layout).map_err(Into::into)
+        });
+    match result {
+        Ok(memory) => {
+            handle.status.set(Status::Ok);
+            memory.as_ptr().cast()
+        }
+        Err(status) => {
+            handle.status.set(status);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the result of the most recent call to [`fixed_bump_alloc`], or
+/// [`Status::Ok`] if there hasn't been one.
+///
+/// # Safety
+///
+/// `bump` must have been returned by [`fixed_bump_new`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn fixed_bump_last_error(bump: *const Handle) -> Status {
+    // SAFETY: Checked by caller.
+    unsafe { &*bump }.status.get()
+}
+
+/// Frees all memory allocated so far, so that it can be reused. The
+/// allocator's chunks are kept for future allocations rather than
+/// deallocated.
+///
+/// # Safety
+///
+/// `bump` must have been returned by [`fixed_bump_new`] and not yet freed,
+/// and memory previously allocated with it must no longer be used.
+#[no_mangle]
+pub unsafe extern "C" fn fixed_bump_reset(bump: *mut Handle) {
+    // SAFETY: Checked by caller.
+    let handle = unsafe { &mut *bump };
+    // Dropping the old allocator returns its chunks to the pool.
+    handle.bump = DynamicBump::with_pool(handle.pool.clone());
+    handle.status.set(Status::Ok);
+}
+
+/// Writes statistics about the allocator to `stats`.
+///
+/// # Safety
+///
+/// `bump` must have been returned by [`fixed_bump_new`] and not yet freed,
+/// and `stats` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fixed_bump_get_stats(
+    bump: *const Handle,
+    stats: *mut Stats,
+) {
+    // SAFETY: Checked by caller.
+    let handle = unsafe { &*bump };
+    let layout = handle.bump.layout();
+    let value = Stats {
+        chunk_size: layout.size(),
+        chunk_align: layout.align(),
+        chunk_bytes: handle.bump.chunk_bytes(),
+        free_chunks: handle.pool.free_chunks(),
+        remaining: handle.bump.remaining_in_current_chunk(),
+    };
+    // SAFETY: Checked by caller.
+    unsafe {
+        stats.write(value);
+    }
+}
+
+/// Frees the allocator and all memory allocated with it. Does nothing if
+/// `bump` is null.
+///
+/// # Safety
+///
+/// `bump` must be null or have been returned by [`fixed_bump_new`] and not
+/// yet freed, and memory allocated with it must no longer be used.
+#[no_mangle]
+pub unsafe extern "C" fn fixed_bump_free(bump: *mut Handle) {
+    if !bump.is_null() {
+        // SAFETY: Checked by caller.
+        drop(unsafe { Box::from_raw(bump) });
+    }
+}
@@ -0,0 +1,231 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use super::budget::Budget;
+use super::generic::{ChunkLayout, ConstChunkLayout, GenericBump};
+use super::methods::{bump_methods, const_layout_methods};
+#[cfg(feature = "std")]
+use super::pool::SyncChunkPool;
+#[cfg(feature = "alloc")]
+use super::pool::{ChunkPool, Pool};
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
+
+struct FixedLayout<const SIZE: usize, const ALIGN: usize>;
+
+impl<const SIZE: usize, const ALIGN: usize> ConstChunkLayout
+    for FixedLayout<SIZE, ALIGN>
+{
+    const LAYOUT: Layout = {
+        assert!(SIZE != 0, "`SIZE` must not be zero");
+        assert!(ALIGN.is_power_of_two(), "`ALIGN` must be a power of two");
+        match Layout::from_size_align(SIZE, ALIGN) {
+            Ok(layout) => layout,
+            Err(_) => panic!("chunk layout is too large"),
+        }
+    };
+}
+
+impl<const SIZE: usize, const ALIGN: usize> ChunkLayout
+    for FixedLayout<SIZE, ALIGN>
+{
+    fn chunk_layout(&self) -> Layout {
+        Self::LAYOUT
+    }
+}
+
+/// Like [`Bump`], but the size and alignment of the chunks are given as
+/// const generic parameters instead of types.
+///
+/// Each chunk has a size of `SIZE` bytes and an alignment of `ALIGN` bytes,
+/// which must be a power of two. Since these are plain numbers, they can be
+/// written directly or computed from other constants, without the need for
+/// helper types like large byte arrays or custom aligned structs:
+///
+/// ```
+/// use fixed_bump::FixedBump;
+///
+/// const PAGE_SIZE: usize = 4096;
+/// let bump = FixedBump::<{ 16 * PAGE_SIZE }, PAGE_SIZE>::new();
+/// let item = bump.alloc_value(123_u64);
+/// assert_eq!(*item, 123);
+/// ```
+///
+/// This type otherwise behaves exactly like [`Bump`]. In particular, chunks
+/// are allocated with the global allocator unless a different
+/// [`ChunkSource`] is given as the type parameter `A` (see [`Self::new_in`]).
+///
+/// [`Bump`]: crate::Bump
+pub struct FixedBump<
+    const SIZE: usize,
+    const ALIGN: usize,
+    A: ChunkSource = Global,
+>(GenericBump<FixedLayout<SIZE, ALIGN>, A>);
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
+impl<const SIZE: usize, const ALIGN: usize> FixedBump<SIZE, ALIGN> {
+    /// Creates a new [`FixedBump`].
+    ///
+    /// `SIZE` must not be zero, `ALIGN` must be a power of two, and a chunk
+    /// (including a small amount of bookkeeping data) must not be too large
+    /// for a [`Layout`]. These conditions are checked at compile time:
+    ///
+    /// ```compile_fail
+    /// let bump = fixed_bump::FixedBump::<0, 1>::new();
+    /// ```
+    pub fn new() -> Self {
+        // Evaluating this constant turns invalid chunk layouts into
+        // compile-time errors.
+        let _ = FixedLayout::<SIZE, ALIGN>::FULL;
+        Self(GenericBump::new(FixedLayout))
+    }
+
+    /// Creates a new [`FixedBump`] with `n` spare chunks already allocated
+    /// (see [`Self::reserve_chunks`]).
+    pub fn with_reserved_chunks(n: usize) -> Self {
+        let bump = Self::new();
+        bump.reserve_chunks(n);
+        bump
+    }
+
+    /// Creates a new [`FixedBump`] that takes chunks from `pool` before
+    /// allocating them, and returns its chunks to `pool` when dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if <code>pool.[layout()]</code> does not have a size of `SIZE`
+    /// and an alignment of `ALIGN`.
+    ///
+    /// [layout()]: ChunkPool::layout
+    pub fn with_pool(pool: ChunkPool) -> Self {
+        Self::with_any_pool(Pool::Local(pool))
+    }
+
+    /// Like [`Self::with_pool`], but uses a [`SyncChunkPool`], which can be
+    /// shared across threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if <code>pool.[layout()]</code> does not have a size of `SIZE`
+    /// and an alignment of `ALIGN`.
+    ///
+    /// [layout()]: SyncChunkPool::layout
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+    pub fn with_sync_pool(pool: SyncChunkPool) -> Self {
+        Self::with_any_pool(Pool::Sync(pool))
+    }
+
+    /// Creates a new [`FixedBump`] whose chunks can have a total size of at
+    /// most `bytes` bytes. See [`Self::set_limit`].
+    pub fn with_limit(bytes: usize) -> Self {
+        let mut bump = Self::new();
+        bump.set_limit(Some(bytes));
+        bump
+    }
+
+    /// Creates a new [`FixedBump`] that uses at most `n` chunks. See
+    /// [`Self::set_max_chunks`].
+    pub fn with_max_chunks(n: usize) -> Self {
+        let mut bump = Self::new();
+        bump.set_max_chunks(Some(n));
+        bump
+    }
+
+    /// Creates a new [`FixedBump`] that draws the size of each of its chunks
+    /// from `budget`, which can be shared with other allocators. See
+    /// [`Self::set_budget`].
+    #[cfg(target_has_atomic = "ptr")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "ptr")))]
+    pub fn with_budget(budget: Budget) -> Self {
+        let mut bump = Self::new();
+        bump.set_budget(Some(budget));
+        bump
+    }
+
+    fn with_any_pool(pool: Pool) -> Self {
+        let layout = FixedLayout;
+        assert!(
+            pool.layout() == layout.chunk_layout(),
+            "pool layout does not match the chunk layout",
+        );
+        Self(GenericBump::with_pool(layout, pool))
+    }
+}
+
+impl<const SIZE: usize, const ALIGN: usize, A: ChunkSource>
+    FixedBump<SIZE, ALIGN, A>
+{
+    /// Like [`FixedBump::new`], but allocates chunks from `source` instead of
+    /// the global allocator. Chunks are returned to `source` when the
+    /// allocator is dropped.
+    pub fn new_in(source: A) -> Self {
+        // See `FixedBump::new`.
+        let _ = FixedLayout::<SIZE, ALIGN>::FULL;
+        Self(GenericBump::new_in(FixedLayout, source))
+    }
+}
+
+bump_methods! {
+    impl[const SIZE: usize, const ALIGN: usize, A: ChunkSource]
+        FixedBump<SIZE, ALIGN, A>,
+    chunk_size = "`SIZE`",
+    chunk_align = "`ALIGN`",
+    example = [
+        "use fixed_bump::FixedBump;",
+        "let bump = FixedBump::<32, 8>::new();",
+    ],
+}
+
+const_layout_methods! {
+    impl[const SIZE: usize, const ALIGN: usize, A: ChunkSource]
+        FixedBump<SIZE, ALIGN, A>,
+    layout = FixedLayout<SIZE, ALIGN>,
+    chunk_size = "`SIZE`",
+    chunk_align = "`ALIGN`",
+    too_small = "FixedBump::<4, 4>::new()",
+    example = "FixedBump::<64, 8>",
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
+impl<const SIZE: usize, const ALIGN: usize> Default
+    for FixedBump<SIZE, ALIGN>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(doc, doctest))]
+/// [`FixedBump`] cannot implement [`Clone`], as this would make it unsound to
+/// implement [`Allocator`](alloc::alloc::Allocator).
+///
+/// ```
+/// use fixed_bump::FixedBump;
+/// struct Test<T = FixedBump<1, 1>>(T);
+/// ```
+///
+/// ```compile_fail
+/// use fixed_bump::FixedBump;
+/// struct Test<T: Clone = FixedBump<1, 1>>(T);
+/// ```
+mod fixed_bump_does_not_impl_clone {}
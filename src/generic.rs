@@ -17,74 +17,551 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use super::budget::Budget;
 use super::chunk::Chunk;
-use super::inner::BumpInner;
-use alloc::alloc::{Layout, handle_alloc_error};
+use super::error::AllocFailure;
+use super::inner::{BumpInner, Failure, SealMode, items_per_chunk};
+#[cfg(feature = "alloc")]
+use super::pool::Pool;
+use super::source::{ChunkSource, Global, handle_alloc_error};
+use core::alloc::Layout;
 use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::mem;
 use core::ptr::NonNull;
 
-/// # Safety
+/// A source of chunk layouts for [`CustomBump`].
 ///
-/// `<Self as Into<Layout>>::into` must not call any method of any
-/// [`GenericBump`].
-pub unsafe trait IntoLayout: Copy + Into<Layout> {}
+/// This trait lets a [`CustomBump`] take its chunk layout from any value,
+/// such as a zero-sized type with a fixed layout or a handle to some runtime
+/// configuration. The allocator asks the source for the layout whenever it
+/// needs it, including when it starts a new chunk, so changes to the
+/// configuration apply to chunks allocated afterwards. Chunks that have
+/// already been allocated keep their original layout.
+///
+/// These methods are never called while the allocator's internal state is
+/// borrowed, so implementations may run arbitrary code (including code that
+/// uses the [`CustomBump`] itself). Calls to them are monomorphized and
+/// inlined like any other generic code: a zero-sized source that returns a
+/// constant layout is as efficient as [`Bump`], which is implemented the same
+/// way.
+///
+/// [`Bump`]: crate::Bump
+/// [`CustomBump`]: crate::CustomBump
+pub trait ChunkLayout {
+    /// Returns the size and alignment of the chunks that should be allocated
+    /// internally by the allocator.
+    fn chunk_layout(&self) -> Layout;
+
+    /// Returns the layout of the next chunk to allocate. This is called right
+    /// before a new chunk is allocated; `size` is the size of the allocation
+    /// that needs the new chunk, and is never greater than the size of
+    /// [`Self::chunk_layout`].
+    ///
+    /// By default, every chunk uses the layout returned by
+    /// [`Self::chunk_layout`]. Implementations can override this to vary the
+    /// size of the chunks, for example to grow them over time. If the
+    /// returned layout can't hold the allocation, the allocation fails as if
+    /// it didn't fit in a chunk.
+    fn next_chunk(&self, size: usize) -> Layout {
+        let _ = size;
+        self.chunk_layout()
+    }
+}
+
+/// A [`ChunkLayout`] whose layout is known at compile time.
+pub trait ConstChunkLayout: ChunkLayout {
+    /// The layout of the chunks. Implementations should panic during
+    /// evaluation if the layout is invalid, so that referring to this
+    /// constant (directly or through [`Self::CHUNK`] or [`Self::FULL`])
+    /// causes a compile-time error.
+    const LAYOUT: Layout;
+
+    /// The layout of each chunk's storage; see [`Chunk::layout`].
+    const CHUNK: Layout = Chunk::layout(Self::LAYOUT);
+
+    /// The layout of each chunk, including its header; see
+    /// [`Chunk::full_layout`].
+    const FULL: Layout = Chunk::full_layout(Self::LAYOUT);
+}
+
+/// Checks at compile time that values of type `T` fit in chunks with layout
+/// `L`.
+pub struct Fits<T, L>(PhantomData<fn() -> (T, L)>);
+
+impl<T, L: ConstChunkLayout> Fits<T, L> {
+    /// Referring to this constant causes a compile-time error if `T` doesn't
+    /// fit.
+    pub const OK: bool = {
+        assert!(
+            mem::size_of::<T>() <= L::CHUNK.size(),
+            "`T` is larger than the chunk size",
+        );
+        assert!(
+            mem::align_of::<T>() <= L::CHUNK.align(),
+            "`T` is more aligned than the chunks",
+        );
+        true
+    };
+}
 
-pub struct GenericBump<L: IntoLayout>(UnsafeCell<BumpInner<L>>);
+/// The implementation shared by the public bump allocator types.
+///
+/// Methods of `A` are called while the [`BumpInner`] is mutably borrowed;
+/// the safety requirements of [`ChunkSource`] ensure they can't call any
+/// methods of [`GenericBump`]. Methods of `L` may run arbitrary code, so they
+/// are called only while no such borrow exists.
+pub struct GenericBump<L: ChunkLayout, A: ChunkSource = Global> {
+    layout: L,
+    inner: UnsafeCell<BumpInner<A>>,
+}
 
-impl<L: IntoLayout> GenericBump<L> {
+/// A method of [`BumpInner`] that allocates memory matching a layout in a new
+/// chunk with another layout, like [`BumpInner::allocate_in_new`].
+type AllocInNew<A> =
+    fn(&mut BumpInner<A>, Layout, Layout) -> Result<NonNull<[u8]>, Failure>;
+
+/// Calls a function with the [`BumpInner`] of a [`GenericBump`] when dropped,
+/// including during unwinding.
+///
+/// The function must not run any code that could possibly call any methods
+/// of [`GenericBump`].
+struct Guard<'a, L, A, F>(&'a GenericBump<L, A>, F)
+where
+    L: ChunkLayout,
+    A: ChunkSource,
+    F: FnMut(&mut BumpInner<A>);
+
+impl<L, A, F> Drop for Guard<'_, L, A, F>
+where
+    L: ChunkLayout,
+    A: ChunkSource,
+    F: FnMut(&mut BumpInner<A>),
+{
+    fn drop(&mut self) {
+        // SAFETY: The function does not run any code that could possibly call
+        // any methods of `GenericBump`, as required by `Guard`.
+        (self.1)(unsafe { &mut *(self.0).inner.get() });
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L: ChunkLayout> GenericBump<L> {
     pub fn new(layout: L) -> Self {
-        Self(UnsafeCell::new(BumpInner::new(layout)))
+        Self::new_in(layout, Global)
     }
 
-    fn inner(&self) -> &BumpInner<L> {
+    pub fn with_pool(layout: L, pool: Pool) -> Self {
+        let mut inner = BumpInner::new(Global);
+        inner.set_pool(pool);
+        Self {
+            layout,
+            inner: UnsafeCell::new(inner),
+        }
+    }
+}
+
+impl<L: ChunkLayout, A: ChunkSource> GenericBump<L, A> {
+    pub fn new_in(layout: L, source: A) -> Self {
+        Self {
+            layout,
+            inner: UnsafeCell::new(BumpInner::new(source)),
+        }
+    }
+
+    fn inner(&self) -> &BumpInner<A> {
         // SAFETY: `BumpInner` has no `&self` methods that could possibly call
         // any methods of `Self`, which ensures we do not concurrently mutably
         // borrow the `UnsafeCell`.
-        unsafe { &*self.0.get() }
+        unsafe { &*self.inner.get() }
     }
 
+    /// The layout currently provided by the layout source.
     pub fn layout(&self) -> Layout {
-        self.inner().layout()
+        self.layout.chunk_layout()
+    }
+
+    /// The layout of the storage of new chunks; see [`Chunk::layout`].
+    fn chunk_layout(&self) -> Layout {
+        Chunk::layout(self.layout())
+    }
+
+    pub fn layout_source(&self) -> &L {
+        &self.layout
+    }
+
+    /// Replaces the layout source, returning the previous one.
+    pub fn set_layout(&mut self, layout: L) -> L {
+        let old = mem::replace(&mut self.layout, layout);
+        let layout = self.layout();
+        self.inner.get_mut().set_layout(layout);
+        old
+    }
+
+    pub fn source_mut(&mut self) -> &mut A {
+        self.inner.get_mut().source_mut()
     }
 
     pub fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
-        // SAFETY: `BumpInner::alloc` does not run any code that could possibly
-        // call any methods of `Self`, which ensures that we do not borrow the
-        // data in the `UnsafeCell` multiple times concurrently.
-        unsafe { &mut *self.0.get() }.allocate(layout)
+        self.try_allocate(layout).ok()
+    }
+
+    pub fn try_allocate(
+        &self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocFailure> {
+        if let Some(memory) = self.allocate_in_current(layout) {
+            return Ok(memory);
+        }
+        self.allocate_in_new(layout, BumpInner::allocate_in_new)
+    }
+
+    /// Allocates memory matching `layout` in a new chunk by calling `alloc`
+    /// with the layout of that chunk, which is obtained from the layout
+    /// source while the [`BumpInner`] is not borrowed.
+    ///
+    /// `alloc` must not run any code that could possibly call any methods of
+    /// `Self`.
+    fn allocate_in_new(
+        &self,
+        layout: Layout,
+        alloc: AllocInNew<A>,
+    ) -> Result<NonNull<[u8]>, AllocFailure> {
+        let cl = self.chunk_layout();
+        if layout.size() > cl.size() || layout.align() > cl.align() {
+            return Err(self.failure(layout, Failure::TooLarge));
+        }
+        // Checking this before calling `L::next_chunk` keeps layout sources
+        // that change their state there (like those of `GrowingBump`) from
+        // doing so when no chunk is allocated.
+        if let Err(e) = self.inner().check_new_chunk() {
+            return Err(self.failure(layout, e));
+        }
+        let chunk = self.layout.next_chunk(layout.size());
+        // SAFETY: The caller guarantees that `alloc` does not run any code
+        // that could possibly call any methods of `Self`.
+        alloc(unsafe { &mut *self.inner.get() }, layout, chunk)
+            .map_err(|e| self.failure(layout, e))
+    }
+
+    /// Converts the reason an allocation of `requested` failed into an
+    /// [`AllocFailure`].
+    fn failure(&self, requested: Layout, failure: Failure) -> AllocFailure {
+        let chunk = self.layout();
+        let cl = Chunk::layout(chunk);
+        match failure {
+            Failure::TooLarge if cl.size() == 0 && requested.size() > 0 => {
+                AllocFailure::ZeroSizedChunk {
+                    requested,
+                    chunk,
+                }
+            }
+            Failure::TooLarge if requested.align() > cl.align() => {
+                AllocFailure::OverAligned {
+                    requested,
+                    chunk,
+                }
+            }
+            Failure::TooLarge => AllocFailure::TooLarge {
+                requested,
+                chunk,
+            },
+            Failure::Sealed => AllocFailure::Sealed {
+                requested,
+                chunk,
+            },
+            Failure::GroupFull => AllocFailure::GroupFull {
+                requested,
+                chunk,
+            },
+            Failure::LimitReached => AllocFailure::BudgetExceeded {
+                requested,
+                chunk,
+            },
+            Failure::SourceFailed => AllocFailure::OutOfMemory {
+                requested,
+                chunk,
+            },
+        }
+    }
+
+    pub fn prefault(&self) -> Option<usize> {
+        self.inner().prefault()
+    }
+
+    pub fn set_prefault(&mut self, page_size: Option<usize>) {
+        self.inner.get_mut().set_prefault(page_size);
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        self.inner().is_sealed()
+    }
+
+    pub fn seal(&self, mode: SealMode) {
+        // SAFETY: `BumpInner::seal` does not run any code that could possibly
+        // call any methods of `Self`.
+        unsafe { &mut *self.inner.get() }.seal(mode);
+    }
+
+    pub fn unseal(&mut self) {
+        self.inner.get_mut().unseal();
+    }
+
+    pub fn spare_chunks(&self) -> usize {
+        self.inner().spare_chunks()
+    }
+
+    pub fn reserve_chunks(&self, n: usize) {
+        let layout = self.layout();
+        // SAFETY: `BumpInner::reserve_chunks` does not run any code that could
+        // possibly call any methods of `Self`.
+        let result =
+            unsafe { &mut *self.inner.get() }.reserve_chunks(n, layout);
+        if let Err(e) = result {
+            // No memory is requested from the chunks themselves, so the
+            // requested layout is empty; `failure` adds the chunk layout.
+            alloc_failed(self.failure(Layout::new::<()>(), e));
+        }
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.inner().limits().max_bytes()
+    }
+
+    pub fn set_limit(&mut self, bytes: Option<usize>) {
+        self.inner.get_mut().limits_mut().set_max_bytes(bytes);
+    }
+
+    pub fn max_chunks(&self) -> Option<usize> {
+        self.inner().limits().max_chunks()
+    }
+
+    pub fn set_max_chunks(&mut self, n: Option<usize>) {
+        self.inner.get_mut().limits_mut().set_max_chunks(n);
+    }
+
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub fn budget(&self) -> Option<&Budget> {
+        self.inner().limits().budget()
+    }
+
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub fn set_budget(&mut self, budget: Option<Budget>) {
+        self.inner.get_mut().limits_mut().set_budget(budget);
+    }
+
+    pub fn chunk_bytes(&self) -> usize {
+        self.inner().limits().bytes()
+    }
+
+    pub fn start_new_chunk(&self) {
+        // SAFETY: `BumpInner::start_new_chunk` does not run any code that
+        // could possibly call any methods of `Self`.
+        unsafe { &mut *self.inner.get() }.start_new_chunk();
+    }
+
+    /// Calls `f` inside an allocation group (see [`BumpInner::begin_group`]).
+    /// If a group is already active, `f` becomes part of that group.
+    pub fn group<R>(&self, f: impl FnOnce() -> R) -> R {
+        if self.inner().in_group() {
+            return f();
+        }
+        // SAFETY: `BumpInner::begin_group` does not run any code that could
+        // possibly call any methods of `Self`.
+        unsafe { &mut *self.inner.get() }.begin_group();
+        let _guard = Guard(self, BumpInner::end_group);
+        f()
+    }
+
+    /// Calls `f` such that allocating a new chunk from the chunk source
+    /// panics, if debug assertions are enabled.
+    pub fn critical_section<R>(&self, f: impl FnOnce() -> R) -> R {
+        // SAFETY: `BumpInner::set_critical` does not run any code that could
+        // possibly call any methods of `Self`.
+        let prev = unsafe { &mut *self.inner.get() }.set_critical(true);
+        let _guard = Guard(self, |inner: &mut BumpInner<A>| {
+            inner.set_critical(prev);
+        });
+        f()
+    }
+
+    pub fn allocate_in_current(
+        &self,
+        layout: Layout,
+    ) -> Option<NonNull<[u8]>> {
+        // SAFETY: `BumpInner::allocate_in_current` does not run any code that
+        // could possibly call any methods of `Self`.
+        unsafe { &mut *self.inner.get() }.allocate_in_current(layout)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc_value_in_current<T>(
+        &self,
+        value: T,
+    ) -> Result<&mut T, T> {
+        let memory = self.allocate_in_current(Layout::new::<T>());
+        // SAFETY: `Self::allocate_in_current`, when not returning `None`, is
+        // guaranteed to return valid memory that matches the provided layout.
+        unsafe { self.init(memory, value) }
     }
 
     #[allow(clippy::mut_from_ref)]
     #[must_use]
     pub fn alloc_value<T>(&self, value: T) -> &mut T {
-        if let Ok(r) = self.try_alloc_value(value) {
-            return r;
+        match self.try_allocate(Layout::new::<T>()) {
+            // SAFETY: `Self::try_allocate`, when successful, is guaranteed to
+            // return valid memory that matches the provided layout.
+            Ok(memory) => unsafe { self.write(memory, value) },
+            Err(e) => alloc_failed(e),
         }
-        if self.can_allocate(Layout::new::<T>()) {
-            handle_alloc_error(Chunk::full_layout(self.inner().layout()));
-        }
-        panic!("this allocator cannot allocate values of this type");
     }
 
     #[allow(clippy::mut_from_ref)]
     pub fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
-        let memory = if let Some(memory) = self.allocate(Layout::new::<T>()) {
-            memory.cast::<T>()
-        } else {
-            return Err(value);
-        };
+        let memory = self.allocate(Layout::new::<T>());
         // SAFETY: `Self::allocate`, when not returning `None`, is guaranteed
-        // to return valid memory that matches the provided layout. Thus, we
-        // can store a value of type `T` in it.
+        // to return valid memory that matches the provided layout.
+        unsafe { self.init(memory, value) }
+    }
+
+    /// Stores `value` in `memory`. `self` is taken only to bound the lifetime
+    /// of the returned reference.
+    ///
+    /// # Safety
+    ///
+    /// `memory` must be `None` or have been returned by an allocation method
+    /// of `self` for a layout matching [`Layout::new::<T>()`].
+    #[allow(clippy::mut_from_ref)]
+    #[allow(clippy::unused_self)]
+    unsafe fn init<T>(
+        &self,
+        memory: Option<NonNull<[u8]>>,
+        value: T,
+    ) -> Result<&mut T, T> {
+        match memory {
+            // SAFETY: Checked by caller.
+            Some(memory) => Ok(unsafe { self.write(memory, value) }),
+            None => Err(value),
+        }
+    }
+
+    /// Like [`Self::init`], but `memory` must not be `None`.
+    ///
+    /// # Safety
+    ///
+    /// `memory` must have been returned by an allocation method of `self` for
+    /// a layout matching [`Layout::new::<T>()`].
+    #[allow(clippy::mut_from_ref)]
+    #[allow(clippy::unused_self)]
+    unsafe fn write<T>(&self, memory: NonNull<[u8]>, value: T) -> &mut T {
+        let memory = memory.cast::<T>();
+        // SAFETY: The caller guarantees `memory` is valid memory that matches
+        // `Layout::new::<T>()`. Thus, we can store a value of type `T` in it.
         unsafe {
             memory.as_ptr().write(value);
         }
         // SAFETY: We just initialized `memory` with `value`.
-        Ok(unsafe { &mut *memory.as_ptr() })
+        unsafe { &mut *memory.as_ptr() }
+    }
+
+    pub fn line_size(&self) -> usize {
+        self.inner().line_size()
+    }
+
+    pub fn set_line_size(&mut self, line: usize) {
+        self.inner.get_mut().set_line_size(line);
+    }
+
+    pub fn allocate_isolated(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        self.try_allocate_isolated(layout).ok()
+    }
+
+    fn try_allocate_isolated(
+        &self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocFailure> {
+        // SAFETY: `BumpInner::allocate_isolated_in_current` and
+        // `BumpInner::current_is_empty` do not run any code that could
+        // possibly call any methods of `Self`.
+        let inner = unsafe { &mut *self.inner.get() };
+        if let Some(memory) = inner.allocate_isolated_in_current(layout) {
+            return Ok(memory);
+        }
+        if inner.current_is_empty() {
+            // A new chunk wouldn't have any more room.
+            return Err(self.failure(layout, Failure::TooLarge));
+        }
+        self.allocate_in_new(layout, BumpInner::allocate_isolated_in_new)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_value_isolated<T>(&self, value: T) -> &mut T {
+        match self.try_allocate_isolated(Layout::new::<T>()) {
+            // SAFETY: `Self::try_allocate_isolated`, when successful, is
+            // guaranteed to return valid memory that matches the provided
+            // layout.
+            Ok(memory) => unsafe { self.write(memory, value) },
+            Err(e) => alloc_failed(e),
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc_value_isolated<T>(&self, value: T) -> Result<&mut T, T> {
+        let memory = self.allocate_isolated(Layout::new::<T>());
+        // SAFETY: `Self::allocate_isolated`, when not returning `None`, is
+        // guaranteed to return valid memory that matches the provided layout.
+        unsafe { self.init(memory, value) }
     }
 
     pub fn can_allocate(&self, layout: Layout) -> bool {
-        let cl = Chunk::layout(self.inner().layout());
+        let cl = self.chunk_layout();
         layout.size() <= cl.size() && layout.align() <= cl.align()
     }
+
+    pub fn remaining_in_current_chunk(&self) -> usize {
+        self.inner().remaining()
+    }
+
+    pub fn would_start_new_chunk(&self, layout: Layout) -> bool {
+        let chunk = self.layout();
+        self.inner().would_start_new_chunk(layout, chunk)
+    }
+
+    pub fn chunk_capacity(&self) -> usize {
+        self.chunk_layout().size()
+    }
+
+    pub fn items_per_chunk<T>(&self) -> usize {
+        items_per_chunk::<T>(self.chunk_layout())
+    }
+}
+
+/// Panics or calls [`handle_alloc_error`] after a failed allocation, as
+/// methods like [`GenericBump::alloc_value`] do.
+pub fn alloc_failed(failure: AllocFailure) -> ! {
+    match failure {
+        AllocFailure::OutOfMemory {
+            chunk,
+            ..
+        } => handle_alloc_error(Chunk::full_layout(chunk)),
+        AllocFailure::Sealed {
+            ..
+        } => panic!("allocator is sealed"),
+        AllocFailure::GroupFull {
+            ..
+        } => panic!("allocation group does not fit in a single chunk"),
+        AllocFailure::BudgetExceeded {
+            ..
+        } => panic!("memory budget exceeded"),
+        AllocFailure::TooLarge {
+            ..
+        }
+        | AllocFailure::OverAligned {
+            ..
+        }
+        | AllocFailure::ZeroSizedChunk {
+            ..
+        } => panic!("this allocator cannot allocate values of this type"),
+    }
 }
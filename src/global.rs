@@ -0,0 +1,285 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::buffer::BufferSource;
+use super::bump::ConstLayout;
+use super::generic::ConstChunkLayout;
+use super::inner::BumpInner;
+use super::source::ChunkSource;
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::hint;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::alloc::System;
+
+/// What a [`GlobalBump`] does with requests that don't fit in its chunks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "8")))]
+#[non_exhaustive]
+pub enum Oversized {
+    /// The allocation fails: [`GlobalAlloc::alloc`] returns a null pointer,
+    /// which usually results in a call to [`handle_alloc_error`].
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    Fail,
+    /// The memory is allocated with the system allocator ([`System`]), and
+    /// is freed when it is deallocated.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+    System,
+}
+
+/// Where a [`GlobalBump`] gets its chunks from.
+enum Memory {
+    #[cfg(feature = "std")]
+    System,
+    Region(*mut [MaybeUninit<u8>]),
+}
+
+enum Source {
+    #[cfg(feature = "std")]
+    System,
+    Region(BufferSource<'static>),
+}
+
+// SAFETY: This impl forwards to `System` or `BufferSource`, both of which
+// uphold the requirements of `ChunkSource`.
+unsafe impl ChunkSource for Source {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::System => System.allocate(layout),
+            Self::Region(source) => source.allocate(layout),
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: Checked by caller.
+        unsafe {
+            match self {
+                #[cfg(feature = "std")]
+                Self::System => System.deallocate(ptr, layout),
+                Self::Region(source) => source.deallocate(ptr, layout),
+            }
+        }
+    }
+}
+
+/// Releases the lock of a [`GlobalBump`] when dropped.
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// A bump allocator that can be used as the global allocator, for programs
+/// that rarely or never free memory.
+///
+/// Like [`Bump`], this allocates memory from fixed-size chunks, whose size
+/// and alignment are those of `Size` and `Align`. The chunks come from a
+/// static region of memory ([`Self::with_region`]) or from the system
+/// allocator (`GlobalBump::new`, which requires the crate feature `std`),
+/// never from the global allocator. Access to the allocator is synchronized
+/// with a spinlock, so it can be shared between threads.
+///
+/// Deallocation frees memory only if it was the most recent allocation in the
+/// current chunk (that is, in last-in, first-out order); otherwise, it is a
+/// no-op, and the memory is not reused. Requests that don't fit in a chunk
+/// are handled according to an [`Oversized`] policy.
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use core::ptr::addr_of_mut;
+/// use fixed_bump::{GlobalBump, Oversized};
+///
+/// const LEN: usize = 1 << 20;
+/// static mut MEMORY: [MaybeUninit<u8>; LEN] = [MaybeUninit::uninit(); LEN];
+///
+/// #[global_allocator]
+/// // SAFETY: `MEMORY` isn't used anywhere else.
+/// static ALLOC: GlobalBump<[u8; 65536], u64> = unsafe {
+///     GlobalBump::with_region(addr_of_mut!(MEMORY), Oversized::Fail)
+/// };
+///
+/// fn main() {
+///     let items: Vec<u32> = (0..100).collect();
+///     assert_eq!(items.iter().sum::<u32>(), 4950);
+/// }
+/// ```
+///
+/// [`Bump`]: crate::Bump
+#[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "8")))]
+pub struct GlobalBump<Size, Align = Size> {
+    locked: AtomicBool,
+    memory: Memory,
+    oversized: Oversized,
+    inner: UnsafeCell<Option<BumpInner<Source>>>,
+    phantom: PhantomData<fn() -> (Size, Align)>,
+}
+
+impl<Size, Align> GlobalBump<Size, Align> {
+    /// Creates a new [`GlobalBump`] whose chunks are allocated with the
+    /// system allocator ([`System`]). Requests that don't fit in a chunk are
+    /// handled according to `oversized`.
+    ///
+    /// This is a `const fn`, so it can be used to initialize a `static`
+    /// marked `#[global_allocator]`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+    pub const fn new(oversized: Oversized) -> Self {
+        Self::with_memory(Memory::System, oversized)
+    }
+
+    /// Creates a new [`GlobalBump`] whose chunks come from `region`, which is
+    /// split into fixed-size blocks (see [`BufferSource`]). Once every block
+    /// is in use, allocations that need a new chunk fail. Requests that
+    /// don't fit in a chunk are handled according to `oversized`.
+    ///
+    /// This is a `const fn`, so it can be used to initialize a `static`
+    /// marked `#[global_allocator]`.
+    ///
+    /// # Safety
+    ///
+    /// `region` must be valid for reads and writes for the rest of the
+    /// program, and it must not be accessed other than through the returned
+    /// allocator (in particular, it must not be passed to another
+    /// [`GlobalBump`]).
+    ///
+    /// [`BufferSource`]: crate::BufferSource
+    pub const unsafe fn with_region(
+        region: *mut [MaybeUninit<u8>],
+        oversized: Oversized,
+    ) -> Self {
+        Self::with_memory(Memory::Region(region), oversized)
+    }
+
+    const fn with_memory(memory: Memory, oversized: Oversized) -> Self {
+        // See `Bump::new`.
+        let _ = ConstLayout::<Size, Align>::FULL;
+        Self {
+            locked: AtomicBool::new(false),
+            memory,
+            oversized,
+            inner: UnsafeCell::new(None),
+            phantom: PhantomData,
+        }
+    }
+
+    /// The policy for requests that don't fit in a chunk.
+    pub fn oversized(&self) -> Oversized {
+        self.oversized
+    }
+
+    fn is_oversized(layout: Layout) -> bool {
+        let chunk = ConstLayout::<Size, Align>::CHUNK;
+        layout.size() > chunk.size() || layout.align() > chunk.align()
+    }
+
+    /// Acquires the lock and calls `f` with the allocator's state, creating
+    /// the state first if this is the first call.
+    fn with_inner<R>(&self, f: impl FnOnce(&mut BumpInner<Source>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(
+                false,
+                true,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        let _unlock = Unlock(&self.locked);
+        // SAFETY: We hold the lock, so no other reference to the state
+        // exists.
+        let inner = unsafe { &mut *self.inner.get() };
+        f(inner.get_or_insert_with(|| {
+            let source = match self.memory {
+                #[cfg(feature = "std")]
+                Memory::System => Source::System,
+                // SAFETY: This runs only once, as the state is created only
+                // once, and the caller of `Self::with_region` guarantees that
+                // the region is valid for the rest of the program and isn't
+                // otherwise accessed.
+                Memory::Region(region) => Source::Region(unsafe {
+                    BufferSource::with_buffer(
+                        ConstLayout::<Size, Align>::LAYOUT,
+                        &mut *region,
+                    )
+                }),
+            };
+            BumpInner::new(source)
+        }))
+    }
+}
+
+// SAFETY: The state of the allocator is accessed only in `Self::with_inner`,
+// while holding the lock. Its chunks can be used from any thread: `System` is
+// thread-safe, and a region is owned exclusively by this allocator.
+unsafe impl<Size, Align> Sync for GlobalBump<Size, Align> {}
+
+// SAFETY: Memory returned by `BumpInner::allocate` matches the given layout
+// and remains valid until the allocator is dropped; chunks are never moved.
+// Oversized requests are forwarded to `System` or fail. `BumpInner` never
+// calls into the global allocator, so allocating can't deadlock.
+unsafe impl<Size, Align> GlobalAlloc for GlobalBump<Size, Align> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if Self::is_oversized(layout) {
+            return match self.oversized {
+                Oversized::Fail => ptr::null_mut(),
+                // SAFETY: Checked by caller.
+                #[cfg(feature = "std")]
+                Oversized::System => unsafe { System.alloc(layout) },
+            };
+        }
+        self.with_inner(|inner| {
+            inner.allocate(layout, ConstLayout::<Size, Align>::LAYOUT)
+        })
+        .map_or(ptr::null_mut(), |memory| memory.as_ptr().cast())
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if Self::is_oversized(layout) {
+            // Oversized memory was allocated with `System`, as allocation
+            // would have failed otherwise.
+            #[cfg(feature = "std")]
+            // SAFETY: Checked by caller.
+            unsafe {
+                System.dealloc(ptr, layout);
+            }
+            return;
+        }
+        // SAFETY: The caller guarantees `ptr` was returned by `Self::alloc`,
+        // which never returns null on success.
+        let ptr = unsafe { NonNull::new_unchecked(ptr) };
+        // SAFETY: The caller guarantees `ptr` was allocated by this allocator
+        // with `layout`, which isn't oversized, so it was returned by
+        // `BumpInner::allocate`. It is no longer in use.
+        self.with_inner(|inner| unsafe {
+            inner.deallocate_last(ptr, layout.size())
+        });
+    }
+}
@@ -0,0 +1,125 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::generic::{ChunkLayout, GenericBump};
+use super::methods::bump_methods;
+use core::alloc::Layout;
+use core::cell::Cell;
+
+struct GrowthLayout {
+    next: Cell<usize>,
+    max: Layout,
+}
+
+impl ChunkLayout for GrowthLayout {
+    fn chunk_layout(&self) -> Layout {
+        self.max
+    }
+
+    /// Grows the chunk size until it is at least `size`, and always uses the
+    /// alignment of `self.max`.
+    fn next_chunk(&self, size: usize) -> Layout {
+        let grow = |n: usize| n.saturating_mul(2).max(1).min(self.max.size());
+        let mut chunk_size = self.next.get();
+        while chunk_size < size {
+            chunk_size = grow(chunk_size);
+        }
+        self.next.set(grow(chunk_size));
+        Layout::from_size_align(chunk_size, self.max.align()).unwrap()
+    }
+}
+
+/// Like [`DynamicBump`], but chunks start small and double in size, up to a
+/// fixed maximum.
+///
+/// The first chunk has the size of the layout passed to [`Self::new`]. Each
+/// subsequent chunk is twice as large as the previous one, until the chunk
+/// size reaches the maximum, after which all chunks have the maximum size.
+/// This way, small arenas don't need to reserve a full-size chunk up front,
+/// while large arenas still end up with fixed-size chunks. All chunks have the
+/// same alignment. If an allocation doesn't fit in the next chunk, that chunk
+/// is grown (by doubling) until it does.
+///
+/// Individual allocations are still performed in non-amortized O(1) time:
+/// the number of doubling steps is bounded by the ratio of the maximum and
+/// initial sizes, so every refill makes exactly one call to the global
+/// allocator, for a chunk no larger than the maximum size, plus at most
+/// log<sub>2</sub>(`max_size` / `initial.size()`) size computations. This is
+/// the worst-case latency of an allocation that needs a new chunk.
+///
+/// [`DynamicBump`]: crate::DynamicBump
+pub struct GrowingBump(GenericBump<GrowthLayout>);
+
+impl GrowingBump {
+    /// Creates a new [`GrowingBump`]. The first chunk allocated internally by
+    /// the allocator will have the size and alignment of `initial`, and chunks
+    /// will grow up to a size of `max_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is less than [`initial.size()`], or if `max_size`
+    /// rounded up to a multiple of [`initial.align()`] would overflow
+    /// [`isize`].
+    ///
+    /// [`initial.size()`]: Layout::size
+    /// [`initial.align()`]: Layout::align
+    pub fn new(initial: Layout, max_size: usize) -> Self {
+        assert!(
+            max_size >= initial.size(),
+            "`max_size` must be at least the initial chunk size",
+        );
+        Self(GenericBump::new(GrowthLayout {
+            next: Cell::new(initial.size()),
+            max: Layout::from_size_align(max_size, initial.align()).unwrap(),
+        }))
+    }
+
+    /// The layout of the largest chunks this allocator will allocate. Its
+    /// size is the `max_size` passed to [`Self::new`], and its alignment is
+    /// that of the initial layout.
+    pub fn max_layout(&self) -> Layout {
+        self.0.layout()
+    }
+}
+
+bump_methods! {
+    impl[] GrowingBump,
+    chunk_size = "the size of [`Self::max_layout`]",
+    chunk_align = "the alignment of [`Self::max_layout`]",
+    example = [
+        "use fixed_bump::GrowingBump;",
+        "# use std::alloc::Layout;",
+        "let bump = GrowingBump::new(Layout::new::<[u64; 4]>(), 32);",
+    ],
+}
+
+#[cfg(any(doc, doctest))]
+/// [`GrowingBump`] cannot implement [`Clone`], as this would make it unsound
+/// to implement [`Allocator`](alloc::alloc::Allocator).
+///
+/// ```
+/// use fixed_bump::GrowingBump;
+/// struct Test<T = GrowingBump>(T);
+/// ```
+///
+/// ```compile_fail
+/// use fixed_bump::GrowingBump;
+/// struct Test<T: Clone = GrowingBump>(T);
+/// ```
+mod growing_bump_does_not_impl_clone {}
@@ -0,0 +1,195 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::bump::ConstLayout;
+use super::chunk::ChunkMemory;
+use super::generic::{ConstChunkLayout, GenericBump};
+use super::inner::items_per_chunk;
+use super::methods::alloc_methods;
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+/// Hands out the inline chunk of an [`InlineBump`] while it is free, and
+/// otherwise allocates chunks with [`Global`].
+struct InlineSource {
+    block: Option<NonNull<u8>>,
+    layout: Layout,
+    free: Cell<bool>,
+}
+
+impl InlineSource {
+    fn new<Size, Align>() -> Self {
+        Self {
+            block: None,
+            layout: Layout::new::<ChunkMemory<Size, Align>>(),
+            free: Cell::new(true),
+        }
+    }
+}
+
+// SAFETY: Chunks other than the inline one come from `Global`. The inline
+// chunk is part of an `InlineBump`, which is only ever accessed through a
+// shared reference (see `InlineBump::with_stack_bump`) and so never moves
+// while its chunks are in use. It isn't handed out again until it is
+// deallocated. Neither method calls into any bump allocator.
+unsafe impl ChunkSource for InlineSource {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        match self.block {
+            Some(block)
+                if self.free.get()
+                    && layout.size() <= self.layout.size()
+                    && layout.align() <= self.layout.align() =>
+            {
+                self.free.set(false);
+                Some(block)
+            }
+            _ => Global.allocate(layout),
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if Some(ptr) == self.block {
+            self.free.set(true);
+        } else {
+            // SAFETY: Every chunk other than the inline one was allocated
+            // with `Global`, and the caller guarantees it is no longer in use.
+            unsafe {
+                Global.deallocate(ptr, layout);
+            }
+        }
+    }
+}
+
+/// Like [`Bump`], but the first chunk is stored inline, so allocators that
+/// never outgrow one chunk don't use the global allocator at all.
+///
+/// Since the first chunk is part of the allocator itself, an [`InlineBump`]
+/// must not move while its memory is in use. It is therefore only available
+/// through a shared reference, inside the closure passed to
+/// [`Self::with_stack_bump`]; the allocator (including its inline chunk) is
+/// stored on the stack for the duration of the call. Once the inline chunk is
+/// full, further chunks are allocated with the global allocator, like those of
+/// a [`Bump`].
+///
+/// ```
+/// use fixed_bump::InlineBump;
+///
+/// let sum = InlineBump::<[u32; 16]>::with_stack_bump(|bump| {
+///     let a = bump.alloc_value(1_u32);
+///     let b = bump.alloc_value(2_u32);
+///     assert!(!bump.spilled());
+///     *a + *b
+/// });
+/// assert_eq!(sum, 3);
+/// ```
+///
+/// Only the allocation methods of [`Bump`] are available; chunk limits,
+/// pools, and other configuration options are not supported.
+///
+/// [`Bump`]: crate::Bump
+pub struct InlineBump<Size, Align = Size> {
+    bump: GenericBump<ConstLayout<Size, Align>, InlineSource>,
+    memory: UnsafeCell<ChunkMemory<Size, Align>>,
+}
+
+impl<Size, Align> InlineBump<Size, Align> {
+    /// Creates a new [`InlineBump`] on the stack and calls `f` with a
+    /// reference to it. The allocator and all of its chunks are dropped when
+    /// `f` returns, so the value returned by `f` cannot borrow from it.
+    ///
+    /// The size of the stack frame includes an entire chunk, so `Size` should
+    /// be kept small enough for the available stack space.
+    pub fn with_stack_bump<R>(f: impl FnOnce(&Self) -> R) -> R {
+        // See `Bump::new`.
+        let _ = ConstLayout::<Size, Align>::FULL;
+        let mut bump = Self {
+            bump: GenericBump::new_in(
+                ConstLayout(PhantomData),
+                InlineSource::new::<Size, Align>(),
+            ),
+            memory: UnsafeCell::new(ChunkMemory::uninit()),
+        };
+        let block = NonNull::new(bump.memory.get().cast());
+        bump.bump.source_mut().block = block;
+        // `bump` doesn't move after this point: `f` receives only a shared
+        // reference, and `bump` is dropped in place when this function
+        // returns or unwinds. Its fields are dropped in order, so the inline
+        // chunk is returned before its memory is freed.
+        f(&bump)
+    }
+
+    /// The number of bytes left in the current chunk, or zero if there is no
+    /// current chunk. Allocating memory whose size is at most this value
+    /// doesn't start a new chunk, but padding needed for alignment may use up
+    /// some of the remaining space.
+    pub fn remaining_in_current_chunk(&self) -> usize {
+        self.bump.remaining_in_current_chunk()
+    }
+
+    /// The size in bytes of each chunk, including the inline one (the size
+    /// of `Size`). This is the largest allocation that can succeed.
+    pub fn chunk_capacity(&self) -> usize {
+        self.bump.chunk_capacity()
+    }
+
+    /// The number of values of type `T` that fit in a single chunk, taking
+    /// into account the padding needed for alignment. This is [`usize::MAX`]
+    /// if `T` is zero-sized, and zero if `T` is more aligned than the chunks.
+    pub const fn items_per_chunk<T>() -> usize {
+        items_per_chunk::<T>(
+            <ConstLayout<Size, Align> as ConstChunkLayout>::CHUNK,
+        )
+    }
+
+    /// Whether the inline chunk has filled up and this allocator has
+    /// allocated chunks with the global allocator.
+    pub fn spilled(&self) -> bool {
+        self.bump.chunk_bytes() > ConstLayout::<Size, Align>::FULL.size()
+    }
+}
+
+// The `BumpAllocator` and `Allocator` implementations generated here rely on
+// `InlineSource` upholding the `ChunkSource` contract: memory in the inline
+// chunk would be invalidated by moving the allocator, but an `InlineBump`
+// can't be moved, as it is only ever accessed through a shared reference
+// (see `InlineBump::with_stack_bump`).
+alloc_methods! {
+    impl[Size, Align] InlineBump<Size, Align>,
+    field = bump,
+    chunk_size = "[`mem::size_of::<Size>()`](core::mem::size_of)",
+    chunk_align = "[`mem::align_of::<Align>()`](core::mem::align_of)",
+}
+
+#[cfg(any(doc, doctest))]
+/// [`InlineBump`] cannot implement [`Clone`], as this would make it unsound
+/// to implement [`Allocator`](alloc::alloc::Allocator).
+///
+/// ```
+/// use fixed_bump::InlineBump;
+/// struct Test<T = InlineBump<u8>>(T);
+/// ```
+///
+/// ```compile_fail
+/// use fixed_bump::InlineBump;
+/// struct Test<T: Clone = InlineBump<u8>>(T);
+/// ```
+mod inline_bump_does_not_impl_clone {}
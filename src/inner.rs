@@ -17,18 +17,24 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use super::chunk::Chunk;
-use alloc::alloc::Layout;
+use super::budget::Limits;
+use super::chunk::{Chunk, ChunkList};
+use super::pool::Pool;
+use super::source::{ChunkSource, Global};
+use core::alloc::Layout;
+use core::mem::{self, ManuallyDrop};
 use core::ptr;
 use core::ptr::NonNull;
 
 /// Returns a pointer matching `layout` if [`layout.align()`] is less than or
 /// equal to <code>[Chunk::layout(cl)].[align()]</code>, where `cl` is the
-/// layout that was passed to [`Chunk::new`]. Otherwise, the returned pointer
-/// will *not* necessarily be aligned to [`layout.align()`].
+/// layout that was passed to [`Chunk::new`] when allocating the chunk that
+/// owns `storage`. Otherwise, the returned pointer will *not* necessarily be
+/// aligned to [`layout.align()`].
 ///
 /// # Safety
 ///
+/// * `storage` must have been returned by [`Chunk::storage`].
 /// * `offset` must be greater than or equal to [`layout.size()`].
 /// * `offset` must be less than or equal to [`Chunk::layout(cl)`], where `cl`
 ///   is the layout that was passed to [`Chunk::new`].
@@ -38,13 +44,12 @@ use core::ptr::NonNull;
 /// [`layout.size()`]: Layout::size
 unsafe fn allocate_in_chunk(
     layout: Layout,
-    chunk: &Chunk,
+    storage: NonNull<u8>,
     offset: &mut usize,
 ) -> NonNull<[u8]> {
     // Round down to a multiple of `layout.align()`. Note that this subtraction
     // will not underflow due to this function's safety requirements.
     let new_offset = (*offset - layout.size()) & !(layout.align() - 1);
-    let storage: NonNull<u8> = chunk.storage();
 
     // SAFETY: `new_offset` must be less than or equal to `offset`, and the
     // caller guarantees that `offset` is less than or equal to the chunk
@@ -63,79 +68,660 @@ unsafe fn allocate_in_chunk(
     unsafe { NonNull::new_unchecked(ptr) }
 }
 
-// Invariant: `offset` is less than or equal to `self.chunk_size()`.
-pub struct BumpInner<L: Copy + Into<Layout>> {
+/// The number of values of type `T` that fit in an empty chunk whose storage
+/// has layout `chunk`, taking into account the rounding performed by
+/// [`allocate_in_chunk`]. Returns [`usize::MAX`] if `T` is zero-sized (and
+/// not more aligned than the chunk).
+pub const fn items_per_chunk<T>(chunk: Layout) -> usize {
+    let size = mem::size_of::<T>();
+    let align = mem::align_of::<T>();
+    if align > chunk.align() {
+        return 0;
+    }
+    if size == 0 {
+        return usize::MAX;
+    }
+    // The first allocation ends at the end of the storage rounded down to a
+    // multiple of `align`. Since `size` is a multiple of `align`, each
+    // allocation after that moves down by exactly `size` bytes.
+    (chunk.size() & !(align - 1)) / size
+}
+
+/// The default cache line size used by isolated allocations, like those made
+/// by [`Bump::alloc_value_isolated`](crate::Bump::alloc_value_isolated).
+///
+/// This is 128 bytes on x86-64 and AArch64, as some of those processors
+/// prefetch cache lines in pairs or have 128-byte cache lines, and 64 bytes on
+/// other architectures.
+pub const CACHE_LINE_SIZE: usize = {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    let size = 128;
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    let size = 64;
+    size
+};
+
+/// Like [`allocate_in_chunk`], but ensures the returned memory doesn't share
+/// any `line`-byte cache line with memory at higher addresses in the chunk
+/// (allocated earlier) or memory at lower addresses (allocated later).
+/// Returns `None` if the allocation doesn't fit in the chunk.
+///
+/// Unlike [`allocate_in_chunk`], the rounding here is based on addresses
+/// rather than offsets, since the storage itself may not be aligned to a
+/// cache line.
+///
+/// # Safety
+///
+/// * `storage` must have been returned by [`Chunk::storage`].
+/// * `offset` must be less than or equal to [`Chunk::layout(cl)`], where `cl`
+///   is the layout that was passed to [`Chunk::new`].
+/// * `line` must be a power of two.
+unsafe fn allocate_isolated_in_chunk(
+    layout: Layout,
+    line: usize,
+    storage: NonNull<u8>,
+    offset: &mut usize,
+) -> Option<NonNull<[u8]>> {
+    let base = storage.as_ptr() as usize;
+    // Round the end of the allocation down to a cache line boundary, so that
+    // it doesn't share a line with the previous allocation.
+    let end = ((base + *offset) & !(line - 1)).checked_sub(base)?;
+    // Round the start down to a cache line boundary (and to the requested
+    // alignment), so that the next allocation won't share a line with it.
+    let align = layout.align().max(line);
+    let start = (base + end.checked_sub(layout.size())?) & !(align - 1);
+    let new_offset = start.checked_sub(base)?;
+
+    // SAFETY: `new_offset` is less than or equal to `offset`, which the
+    // caller guarantees is less than or equal to the chunk layout's size.
+    let start = unsafe { storage.as_ptr().add(new_offset) };
+    *offset = new_offset;
+
+    // Note: `end - new_offset` is at least `layout.size()`, and `end` is less
+    // than or equal to the original value of `offset`, so the returned slice
+    // points to valid (but possibly uninitialized) memory.
+    let ptr = ptr::slice_from_raw_parts_mut(start, end - new_offset);
+    // SAFETY: `storage` is non-null, so `ptr` must also be non-null.
+    Some(unsafe { NonNull::new_unchecked(ptr) })
+}
+
+/// Why [`BumpInner`] failed to allocate memory or obtain chunks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The allocation doesn't fit in an empty chunk.
+    TooLarge,
+    /// The allocator is sealed.
+    Sealed,
+    /// The allocation doesn't fit in the chunk of the current allocation
+    /// group.
+    GroupFull,
+    /// A new chunk would have exceeded the limits.
+    LimitReached,
+    /// The chunk source failed.
+    SourceFailed,
+}
+
+/// What happens when memory is allocated from a sealed allocator. See
+/// [`Bump::seal_with`](crate::Bump::seal_with).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SealMode {
+    /// Allocation fails, as if the global allocator had failed.
+    Fail,
+    /// Allocation panics.
+    Panic,
+    /// Allocation panics if debug assertions are enabled, and fails
+    /// otherwise.
+    DebugPanic,
+}
+
+// Invariants:
+//
+// * `self.chunk` is the most recently allocated chunk; the remaining chunks
+//   can be reached through [`Chunk::take_prev`].
+// * If `self.current` is not `None`, it is the storage of `self.chunk`, and
+//   `self.align` is the alignment of that storage.
+// * `self.offset` is less than or equal to the size of the storage
+//   `self.current` points to (or zero if `self.current` is `None`).
+// * If `self.group` is true, a new chunk is allocated only if `self.current`
+//   is `None`.
+// * `self.line` is a power of two.
+// * All chunks in `self.spare` have the same layout.
+// * All chunks in `self.chunk` and `self.spare` were allocated from
+//   `self.source`, and `self.spare` is emptied before it is dropped.
+// * If `self.pool` is not `None`, `A` is `Global`, and chunks with the same
+//   layout as the pool are returned to the pool when this type is dropped.
+// * `self.limits` counts every chunk in `self.chunk` and `self.spare`.
+// * If `self.seal` is not `None`, `self.current` is `None`, and `self.saved`
+//   holds the values of `self.current` and `self.offset` from before the
+//   allocator was sealed, or `None` if they should not be restored.
+pub struct BumpInner<A: ChunkSource = Global> {
     chunk: Option<Chunk>,
+    current: Option<NonNull<u8>>,
     offset: usize,
-    layout: L,
+    align: usize,
+    group: bool,
+    line: usize,
+    spare: ChunkList,
+    pool: Option<Pool>,
+    critical: bool,
+    seal: Option<SealMode>,
+    saved: Option<(NonNull<u8>, usize)>,
+    prefault: Option<usize>,
+    limits: Limits,
+    source: A,
+}
+
+#[cfg(feature = "alloc")]
+impl BumpInner {
+    /// Sets the pool that chunks are taken from and returned to.
+    pub fn set_pool(&mut self, pool: Pool) {
+        self.pool = Some(pool);
+    }
 }
 
-impl<L: Copy + Into<Layout>> BumpInner<L> {
-    pub fn new(layout: L) -> Self {
+impl<A: ChunkSource> BumpInner<A> {
+    pub fn new(source: A) -> Self {
         Self {
             chunk: None,
+            current: None,
             offset: 0,
-            layout,
+            align: 1,
+            group: false,
+            line: CACHE_LINE_SIZE,
+            spare: ChunkList::new(),
+            pool: None,
+            critical: false,
+            seal: None,
+            saved: None,
+            prefault: None,
+            limits: Limits::default(),
+            source,
+        }
+    }
+
+    /// Prepares for chunks allocated from now on to use `layout`. Existing
+    /// chunks keep their original layout. If the current chunk isn't aligned
+    /// enough for the new layout, allocation moves on to a new chunk. Spare
+    /// chunks with a different layout are deallocated.
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.release_stale_spares(layout);
+        if self.chunk.is_some() && self.align < Chunk::layout(layout).align() {
+            self.retire();
         }
     }
 
-    pub fn layout(&self) -> Layout {
-        self.layout.into()
+    /// Deallocates the spare chunks if they don't have layout `layout`.
+    fn release_stale_spares(&mut self, layout: Layout) {
+        if self.spare.first_layout().map_or(false, |l| l != layout) {
+            let spare = mem::take(&mut self.spare);
+            self.release(spare);
+        }
+    }
+
+    /// Stops allocating in the current chunk, which stays allocated until the
+    /// allocator is dropped.
+    fn retire(&mut self) {
+        self.current = None;
+        self.offset = 0;
+        self.saved = None;
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        self.seal.is_some()
+    }
+
+    /// Makes all further allocations fail (or panic, depending on `mode`)
+    /// until [`Self::unseal`] is called.
+    pub fn seal(&mut self, mode: SealMode) {
+        if self.seal.is_none() {
+            self.saved = self.current.take().map(|c| (c, self.offset));
+            self.offset = 0;
+        }
+        self.seal = Some(mode);
     }
 
-    fn chunk_size(&self) -> usize {
-        Chunk::layout(self.layout()).size()
+    pub fn unseal(&mut self) {
+        if self.seal.take().is_some() {
+            if let Some((current, offset)) = self.saved.take() {
+                self.current = Some(current);
+                self.offset = offset;
+            }
+        }
+    }
+
+    pub fn line_size(&self) -> usize {
+        self.line
     }
 
-    fn chunk_align(&self) -> usize {
-        Chunk::layout(self.layout()).align()
+    /// Sets the cache line size used by [`Self::allocate_isolated`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line` is not a power of two.
+    pub fn set_line_size(&mut self, line: usize) {
+        assert!(
+            line.is_power_of_two(),
+            "cache line size must be a power of two"
+        );
+        self.line = line;
+    }
+
+    /// Whether there is a current chunk in which nothing has been allocated
+    /// yet.
+    pub fn current_is_empty(&self) -> bool {
+        match (&self.chunk, self.current) {
+            (Some(chunk), Some(_)) => {
+                self.offset == Chunk::layout(chunk.requested_layout()).size()
+            }
+            _ => false,
+        }
     }
 
-    /// Returns a pointer to memory matching `layout`, or `None` if the
-    /// allocation fails.
-    pub fn allocate(&mut self, layout: Layout) -> Option<NonNull<[u8]>> {
-        if layout.align() > self.chunk_align() {
+    /// Makes the next allocation start at the beginning of a new chunk,
+    /// unless nothing has been allocated in the current chunk yet.
+    pub fn start_new_chunk(&mut self) {
+        if !self.current_is_empty() {
+            self.retire();
+        }
+    }
+
+    pub fn in_group(&self) -> bool {
+        self.group
+    }
+
+    /// Starts an allocation group: allocations made until [`Self::end_group`]
+    /// is called all come from a single chunk, which is new unless the
+    /// current chunk is still empty.
+    pub fn begin_group(&mut self) {
+        self.start_new_chunk();
+        self.group = true;
+    }
+
+    pub fn end_group(&mut self) {
+        self.group = false;
+    }
+
+    /// The number of bytes left in the current chunk, or zero if there is no
+    /// current chunk. Allocations may use fewer bytes than this due to
+    /// alignment.
+    pub fn remaining(&self) -> usize {
+        // `self.offset` is zero when `self.current` is `None`.
+        self.offset
+    }
+
+    /// Frees the memory at `ptr` if it was the most recent allocation in the
+    /// current chunk, so that the next allocation can reuse it. Returns
+    /// whether the memory was freed.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`Self::allocate`] for a layout with
+    /// a size of `size`, and the memory must no longer be in use.
+    #[cfg(target_has_atomic = "8")]
+    pub unsafe fn deallocate_last(
+        &mut self,
+        ptr: NonNull<u8>,
+        size: usize,
+    ) -> bool {
+        let storage = if let Some(storage) = self.current {
+            storage
+        } else {
+            return false;
+        };
+        if ptr.as_ptr() != storage.as_ptr().wrapping_add(self.offset) {
+            return false;
+        }
+        // The most recent allocation starts at `self.offset`, and it ends at
+        // or before the offset from before it was made, so this doesn't
+        // exceed the size of the chunk.
+        self.offset += size;
+        true
+    }
+
+    /// Whether [`Self::allocate`] would need a new chunk to allocate memory
+    /// matching `layout`, if new chunks have layout `chunk`. This is false if
+    /// the allocation would fail without trying to obtain a new chunk.
+    pub fn would_start_new_chunk(
+        &self,
+        layout: Layout,
+        chunk: Layout,
+    ) -> bool {
+        let chunk = Chunk::layout(chunk);
+        if layout.align() > chunk.align() {
+            return false;
+        }
+        if self.fits_in_current(layout) {
+            return false;
+        }
+        layout.size() <= chunk.size()
+            && !self.group_is_full()
+            && self.seal.is_none()
+    }
+
+    /// Whether memory matching `layout` can be allocated in the current
+    /// chunk.
+    fn fits_in_current(&self, layout: Layout) -> bool {
+        // `self.offset` is zero when `self.current` is `None`.
+        self.current.is_some()
+            && self.offset >= layout.size()
+            && layout.align() <= self.align
+    }
+
+    /// Whether allocations that don't fit in the current chunk fail because
+    /// the current allocation group has already used up its chunk.
+    pub fn group_is_full(&self) -> bool {
+        self.group && self.current.is_some()
+    }
+
+    /// Sets whether allocating a new chunk from the chunk source should
+    /// panic (if debug assertions are enabled). Returns the previous value.
+    pub fn set_critical(&mut self, critical: bool) -> bool {
+        mem::replace(&mut self.critical, critical)
+    }
+
+    /// Like [`Self::allocate`], but fails instead of moving on to a new chunk
+    /// when `layout` doesn't fit in the current chunk.
+    pub fn allocate_in_current(
+        &mut self,
+        layout: Layout,
+    ) -> Option<NonNull<[u8]>> {
+        if !self.fits_in_current(layout) {
             return None;
         }
+        let storage = self.current?;
+        // SAFETY: `self.offset` is always less than or equal to the size of
+        // the current chunk due to this type's invariants, and we just ensured
+        // that `self.offset` is at least `layout.size()`. The current chunk is
+        // aligned enough for `layout`.
+        Some(unsafe { allocate_in_chunk(layout, storage, &mut self.offset) })
+    }
+
+    /// Returns a pointer to memory matching `layout`. If it doesn't fit in
+    /// the current chunk, a new chunk with layout `chunk` is used.
+    #[cfg(target_has_atomic = "8")]
+    pub fn allocate(
+        &mut self,
+        layout: Layout,
+        chunk: Layout,
+    ) -> Result<NonNull<[u8]>, Failure> {
+        match self.allocate_in_current(layout) {
+            Some(memory) => Ok(memory),
+            None => self.allocate_in_new(layout, chunk),
+        }
+    }
+
+    /// Allocates memory matching `layout` at the start of a new chunk with
+    /// layout `chunk`. Fails with [`Failure::TooLarge`] if the allocation
+    /// doesn't fit in such a chunk.
+    pub fn allocate_in_new(
+        &mut self,
+        layout: Layout,
+        chunk: Layout,
+    ) -> Result<NonNull<[u8]>, Failure> {
+        let storage_layout = Chunk::layout(chunk);
+        if layout.size() > storage_layout.size()
+            || layout.align() > storage_layout.align()
+        {
+            return Err(Failure::TooLarge);
+        }
+        let storage = self.new_chunk(chunk)?;
+        // SAFETY: `self.offset` is the size of the new chunk's storage, which
+        // we just ensured is at least `layout.size()`, and the storage is
+        // aligned enough for `layout`.
+        Ok(unsafe { allocate_in_chunk(layout, storage, &mut self.offset) })
+    }
 
-        if let Some(chunk) = self.chunk.as_mut() {
-            if self.offset >= layout.size() {
-                // SAFETY: `self.offset` is always less than or equal to
-                // `self.chunk_size()` due to this type's invariants, and we
-                // just ensured that `self.offset` is at least `layout.size()`.
-                return Some(unsafe {
-                    allocate_in_chunk(layout, chunk, &mut self.offset)
-                });
+    /// Checks whether an allocation that doesn't fit in the current chunk may
+    /// start a new one.
+    ///
+    /// If the allocator is sealed, this fails or panics according to the
+    /// [`SealMode`].
+    pub fn check_new_chunk(&self) -> Result<(), Failure> {
+        if self.group_is_full() {
+            return Err(Failure::GroupFull);
+        }
+        self.check_seal()
+    }
+
+    /// Like [`Self::allocate_in_current`], but the returned memory doesn't
+    /// share any cache line (of size `self.line`) with other allocations.
+    pub fn allocate_isolated_in_current(
+        &mut self,
+        layout: Layout,
+    ) -> Option<NonNull<[u8]>> {
+        if layout.align() > self.align {
+            return None;
+        }
+        let storage = self.current?;
+        // SAFETY: `self.offset` is always less than or equal to the size of
+        // the current chunk, and `self.line` is a power of two, due to this
+        // type's invariants.
+        unsafe {
+            allocate_isolated_in_chunk(
+                layout,
+                self.line,
+                storage,
+                &mut self.offset,
+            )
+        }
+    }
+
+    /// Like [`Self::allocate_in_new`], but the returned memory doesn't share
+    /// any cache line (of size `self.line`) with other allocations. Fails
+    /// with [`Failure::TooLarge`] if the padded allocation doesn't fit in an
+    /// empty chunk.
+    pub fn allocate_isolated_in_new(
+        &mut self,
+        layout: Layout,
+        chunk: Layout,
+    ) -> Result<NonNull<[u8]>, Failure> {
+        let storage_layout = Chunk::layout(chunk);
+        if layout.size() > storage_layout.size()
+            || layout.align() > storage_layout.align()
+        {
+            return Err(Failure::TooLarge);
+        }
+        let storage = self.new_chunk(chunk)?;
+        // SAFETY: `self.offset` is the size of the new chunk's storage, and
+        // `self.line` is a power of two due to this type's invariants.
+        unsafe {
+            allocate_isolated_in_chunk(
+                layout,
+                self.line,
+                storage,
+                &mut self.offset,
+            )
+        }
+        .ok_or(Failure::TooLarge)
+    }
+
+    pub fn spare_chunks(&self) -> usize {
+        self.spare.len()
+    }
+
+    /// Obtains spare chunks with layout `layout` until there are at least
+    /// `n` (see [`Self::obtain_chunk`]). Spare chunks with a different layout
+    /// are deallocated first.
+    ///
+    /// If the allocator is sealed, this fails or panics according to the
+    /// [`SealMode`].
+    pub fn reserve_chunks(
+        &mut self,
+        n: usize,
+        layout: Layout,
+    ) -> Result<(), Failure> {
+        self.check_seal()?;
+        self.release_stale_spares(layout);
+        while self.spare.len() < n {
+            let chunk = self.obtain_chunk(layout)?;
+            // SAFETY: The chunk was just obtained, so it is not in use.
+            // `self.spare` is emptied before it is dropped.
+            unsafe {
+                self.spare.push(chunk);
             }
         }
+        Ok(())
+    }
 
-        if layout.size() > self.chunk_size() {
+    /// Takes a chunk from the pool, if there is one with layout `layout`.
+    fn take_pooled(&self, layout: Layout) -> Option<Chunk> {
+        let pool = self.pool.as_ref()?;
+        if pool.layout() != layout {
             return None;
         }
+        pool.take()
+    }
+
+    /// Stops counting `chunks` against the limits, and returns them to the
+    /// pool, or deallocates them if they don't match the pool's layout (or if
+    /// there is no pool). `chunks` must have been allocated from
+    /// `self.source`.
+    fn release(&mut self, chunks: ChunkList) {
+        // If deallocating a chunk panics, the remaining chunks are leaked
+        // rather than deallocated with the global allocator by `ChunkList`'s
+        // destructor.
+        let mut chunks = ManuallyDrop::new(chunks);
+        while let Some(chunk) = chunks.pop() {
+            let layout = chunk.requested_layout();
+            self.limits.release(Chunk::full_layout(layout).size());
+            let pool = self.pool.as_ref().filter(|p| p.layout() == layout);
+            if let Some(pool) = pool {
+                // SAFETY: Chunks in a `ChunkList` are not in use, and we
+                // just checked that the layout matches. There is a pool, so
+                // `A` is `Global`, and the chunk was allocated by
+                // `Chunk::new`.
+                unsafe {
+                    pool.give(chunk);
+                }
+            } else {
+                // SAFETY: Chunks in a `ChunkList` are not in use, and the
+                // caller guarantees the chunk was allocated from
+                // `self.source`.
+                unsafe {
+                    chunk.drop_in(&self.source);
+                }
+            }
+        }
+    }
+
+    pub fn prefault(&self) -> Option<usize> {
+        self.prefault
+    }
+
+    /// Sets the page size with which new chunks are prefaulted (see
+    /// [`Chunk::prefault`]), or disables prefaulting if `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the page size is zero.
+    pub fn set_prefault(&mut self, page_size: Option<usize>) {
+        assert!(page_size != Some(0), "page size must not be zero");
+        self.prefault = page_size;
+    }
+
+    /// Allocates a chunk from `self.source`.
+    fn alloc_chunk(&self, layout: Layout) -> Option<Chunk> {
+        debug_assert!(
+            !self.critical,
+            "new chunk allocated inside a critical section",
+        );
+        let chunk = Chunk::new_in(layout, &self.source)?;
+        if let Some(page_size) = self.prefault {
+            chunk.prefault(page_size);
+        }
+        Some(chunk)
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub fn source_mut(&mut self) -> &mut A {
+        &mut self.source
+    }
+
+    pub fn limits_mut(&mut self) -> &mut Limits {
+        &mut self.limits
+    }
+
+    /// Obtains a chunk with layout `layout` from the pool if possible, and
+    /// otherwise from the chunk source. Fails if the chunk would exceed the
+    /// limits or if the chunk source fails.
+    fn obtain_chunk(&mut self, layout: Layout) -> Result<Chunk, Failure> {
+        // The header is counted too, so that the limits bound the memory
+        // actually used by the chunks.
+        let size = Chunk::full_layout(layout).size();
+        if !self.limits.try_charge(size) {
+            return Err(Failure::LimitReached);
+        }
+        let chunk =
+            self.take_pooled(layout).or_else(|| self.alloc_chunk(layout));
+        if chunk.is_none() {
+            self.limits.release(size);
+        }
+        chunk.ok_or(Failure::SourceFailed)
+    }
 
-        let chunk_size = self.chunk_size();
-        let chunk = self.chunk.take();
-        let chunk = self.chunk.insert(Chunk::new(self.layout(), chunk)?);
-        self.offset = chunk_size;
+    /// Succeeds if the allocator isn't sealed. Otherwise, panics or fails
+    /// according to the [`SealMode`].
+    fn check_seal(&self) -> Result<(), Failure> {
+        match self.seal {
+            None => Ok(()),
+            Some(SealMode::Fail) => Err(Failure::Sealed),
+            Some(SealMode::Panic) => panic!("allocator is sealed"),
+            Some(SealMode::DebugPanic) => {
+                debug_assert!(false, "allocator is sealed");
+                Err(Failure::Sealed)
+            }
+        }
+    }
+
+    /// Takes a spare chunk, if there is one with layout `layout`.
+    fn take_spare(&mut self, layout: Layout) -> Option<Chunk> {
+        if self.spare.first_layout()? != layout {
+            return None;
+        }
+        self.spare.pop()
+    }
 
-        // SAFETY: `self.offset` is always less than or equal to
-        // `self.chunk_size()` due to this type's invariants, and we ensured
-        // that `self.chunk_size()` (the current value of `self.offset`) is at
-        // least `layout.size()` above.
-        Some(unsafe { allocate_in_chunk(layout, chunk, &mut self.offset) })
+    /// Makes a new chunk with layout `chunk_layout` the current chunk,
+    /// returning its storage. A spare chunk is used if possible; otherwise, a
+    /// chunk is obtained with [`Self::obtain_chunk`].
+    ///
+    /// Fails if a new chunk may not be started (see
+    /// [`Self::check_new_chunk`]).
+    fn new_chunk(
+        &mut self,
+        chunk_layout: Layout,
+    ) -> Result<NonNull<u8>, Failure> {
+        self.check_new_chunk()?;
+        let mut chunk = match self.take_spare(chunk_layout) {
+            Some(chunk) => chunk,
+            None => self.obtain_chunk(chunk_layout)?,
+        };
+        chunk.set_prev(self.chunk.take());
+        let storage = self.chunk.insert(chunk).storage();
+        self.current = Some(storage);
+        let storage_layout = Chunk::layout(chunk_layout);
+        self.offset = storage_layout.size();
+        self.align = storage_layout.align();
+        Ok(storage)
     }
 }
 
-impl<L: Copy + Into<Layout>> Drop for BumpInner<L> {
+impl<A: ChunkSource> Drop for BumpInner<A> {
     fn drop(&mut self) {
+        let mut chunks = mem::take(&mut self.spare);
         let mut tail = self.chunk.take();
         while let Some(mut chunk) = tail {
-            let prev = chunk.take_prev();
-            // SAFETY: All chunks are allocated with `self.layout`.
+            tail = chunk.take_prev();
+            // SAFETY: The memory in the chunk is no longer in use, as all
+            // references to it are bound by the lifetime of the allocator.
+            // `Self::release` empties `chunks`.
             unsafe {
-                chunk.drop(self.layout());
+                chunks.push(chunk);
             }
-            tail = prev;
         }
+        self.release(chunks);
     }
 }
@@ -44,10 +44,12 @@
 //! [`Bump::allocate`] for the conditions under which allocation may fail.
 //!
 //! This crate depends only on [`core`] and [`alloc`], so it can be used in
-//! `no_std` environments that support [`alloc`].
+//! `no_std` environments that support [`alloc`]. Without the crate feature
+//! `alloc`, it depends only on [`core`] (see [Crate features]).
 //!
 //! [`core`]: https://doc.rust-lang.org/core/
 //! [`alloc`]: https://doc.rust-lang.org/alloc/
+//! [Crate features]: #crate-features
 //!
 //! Example
 //! -------
@@ -113,13 +115,39 @@
 //! Crate features
 //! --------------
 //!
+//! The crate feature `alloc` is enabled by default. If it is disabled, this
+//! crate doesn’t use a global allocator, and chunks must instead come from
+//! caller-provided memory with [`BufferSource`], which splits that memory
+//! into fixed-size blocks and fails once they are all in use. Types that
+//! require heap allocation, such as [`ChunkPool`] and [`GrowingBump`], are
+//! unavailable, as are constructors like [`Bump::new`] that use the global
+//! allocator; use [`Bump::new_in`] or [`DynamicBump::new_in`] instead.
+//!
+//! The `alloc` feature was added in version 0.4; in earlier versions, the
+//! global allocator was always used. Crates that depend on this crate with
+//! `default-features = false` must enable `alloc` explicitly to keep using it.
+//! This makes 0.4 incompatible with 0.3 (see `CHANGELOG.md`).
+//!
 //! If the crate feature `allocator_api` is enabled, the unstable [`Allocator`]
 //! trait will be implemented for `T`, `&T`, and [`crate::Rc<T>`], where `T` is
-//! [`Bump`] or [`DynamicBump`]. This lets you use those types as allocators
-//! for various data structures like [`Box`] and [`Vec`]. Note that this
-//! feature requires Rust nightly. Alternatively, if the feature
-//! `allocator-fallback` is enabled, this crate will use the allocator API
-//! provided by [allocator-fallback] instead of the standard library’s.
+//! any of this crate’s bump allocators, such as [`Bump`] or [`DynamicBump`].
+//! This lets you use those types as allocators for various data structures
+//! like [`Box`] and [`Vec`]. Note that this feature requires Rust nightly.
+//! Alternatively, if the feature `allocator-fallback` is enabled, this crate
+//! will use the allocator API provided by [allocator-fallback] instead of the
+//! standard library’s.
+//!
+//! If the crate feature `std` is enabled, `SyncChunkPool`, a thread-safe
+//! version of [`ChunkPool`], will be available, along with a background thread
+//! that keeps such a pool filled with free chunks. [`GlobalBump`], which is
+//! available on targets that support atomic operations on bytes, will also be
+//! able to allocate its chunks with the system allocator.
+//!
+//! If the crate feature `ffi` is enabled, the module `ffi` provides a C API
+//! for creating and using dynamically sized bumps from other languages. A C
+//! header is available in `include/fixed_bump.h`, and the `fixed-bump-ffi`
+//! crate in the repository’s `ffi` directory builds the API as a static or
+//! shared library.
 //!
 //! [allocator-fallback]: https://docs.rs/allocator-fallback
 //!
@@ -135,17 +163,73 @@ use alloc::alloc::{AllocError, Allocator};
 #[cfg(feature = "allocator-fallback")]
 use allocator_fallback::{AllocError, Allocator};
 
+// Documentation links to items in `alloc` even when it isn't used.
+#[cfg(any(feature = "alloc", doc))]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+mod budget;
+mod buffer;
 mod bump;
 mod chunk;
+mod custom;
 mod dynamic;
+mod error;
+#[cfg(feature = "ffi")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "ffi")))]
+pub mod ffi;
+mod fixed;
 mod generic;
+#[cfg(target_has_atomic = "8")]
+mod global;
+#[cfg(feature = "alloc")]
+mod growing;
+mod inline;
 mod inner;
+mod methods;
+mod pool;
+mod proxy;
+#[cfg(feature = "alloc")]
 mod rc;
+#[cfg(feature = "alloc")]
+mod segregated;
+mod source;
+mod spec;
+mod traits;
 
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use budget::Budget;
+pub use buffer::BufferSource;
 pub use bump::Bump;
+pub use custom::CustomBump;
 pub use dynamic::DynamicBump;
+pub use error::{AllocFailure, ChunkLayoutError};
+pub use fixed::FixedBump;
+pub use generic::ChunkLayout;
+#[cfg(target_has_atomic = "8")]
+pub use global::{GlobalBump, Oversized};
+#[cfg(feature = "alloc")]
+pub use growing::GrowingBump;
+pub use inline::InlineBump;
+pub use inner::{CACHE_LINE_SIZE, SealMode};
+#[cfg(feature = "alloc")]
+pub use pool::ChunkPool;
+#[cfg(feature = "std")]
+pub use pool::{Refiller, SyncChunkPool};
+#[doc(hidden)]
+pub use proxy::ChunkUnion;
+pub use proxy::{
+    Align8, Align16, Align32, Align64, Align128, Align256, Align512,
+    Align1024, Align2048, Align4096,
+};
+#[cfg(feature = "alloc")]
 pub use rc::Rc;
+#[cfg(feature = "alloc")]
 #[allow(deprecated)]
 pub use rc::RcBump;
+#[cfg(feature = "alloc")]
+pub use segregated::{SegregatedBump, SizeClass};
+pub use source::{ChunkSource, Global};
+pub use spec::{ChunkSpec, ParseChunkSpecError};
+pub use traits::BumpAllocator;
@@ -0,0 +1,810 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Macros that generate the methods shared by the bump allocators that wrap
+//! a [`GenericBump`](crate::generic::GenericBump).
+
+/// Generates the [`BumpAllocator`] and [`Allocator`] implementations of a
+/// bump allocator, which forward to its inherent `allocate`, `try_allocate`,
+/// `alloc_value`, `try_alloc_value`, and `can_allocate` methods.
+///
+/// [`BumpAllocator`]: crate::BumpAllocator
+/// [`Allocator`]: alloc::alloc::Allocator
+macro_rules! allocator_impls {
+    (impl[$($gen:tt)*] $name:ident$(<$($param:tt),*>)? $(,)?) => {
+        // SAFETY: These methods forward to the inherent methods of the
+        // allocator, which return memory that remains valid until the
+        // allocator is dropped. All returned memory is allocated from the
+        // chunk source, which guarantees that moving it doesn't invalidate
+        // the memory.
+        unsafe impl<$($gen)*> $crate::BumpAllocator
+            for $name$(<$($param),*>)?
+        {
+            fn allocate(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Option<core::ptr::NonNull<[u8]>> {
+                Self::allocate(self, layout)
+            }
+
+            fn try_allocate(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, $crate::AllocFailure> {
+                Self::try_allocate(self, layout)
+            }
+
+            fn alloc_value<T>(&self, value: T) -> &mut T {
+                Self::alloc_value(self, value)
+            }
+
+            fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
+                Self::try_alloc_value(self, value)
+            }
+
+            fn can_allocate(&self, layout: core::alloc::Layout) -> bool {
+                Self::can_allocate(self, layout)
+            }
+        }
+
+        #[cfg(any(feature = "allocator_api", feature = "allocator-fallback"))]
+        #[cfg_attr(
+            feature = "doc_cfg",
+            doc(cfg(any(
+                feature = "allocator_api",
+                feature = "allocator-fallback",
+            )))
+        )]
+        // SAFETY: `Self::allocate` (when not returning `None`) returns
+        // pointers to valid memory that matches the provided `Layout`.
+        //
+        // The allocator cannot be cloned, as it does not implement `Clone`.
+        // Moving it will not invalidate any returned memory, as all returned
+        // memory is allocated from the chunk source, which guarantees that
+        // moving it doesn't invalidate the memory.
+        unsafe impl<$($gen)*> $crate::Allocator for $name$(<$($param),*>)? {
+            fn allocate(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, $crate::AllocError> {
+                self.allocate(layout).ok_or($crate::AllocError)
+            }
+
+            unsafe fn deallocate(
+                &self,
+                _ptr: core::ptr::NonNull<u8>,
+                _layout: core::alloc::Layout,
+            ) {
+                // No-op: the allocator deallocates all its memory when
+                // dropped.
+            }
+        }
+    };
+}
+
+pub(crate) use allocator_impls;
+
+/// Generates the allocation methods of a bump allocator that forwards to a
+/// single [`GenericBump`], along with its [`BumpAllocator`] and [`Allocator`]
+/// implementations (see [`allocator_impls!`]).
+///
+/// `field` is the field of the type that holds the [`GenericBump`], and
+/// `chunk_size` and `chunk_align` describe the size and alignment of the
+/// chunks in the documentation. `errors`, if present, contains extra lines
+/// for the documentation of `try_allocate`'s errors.
+///
+/// [`BumpAllocator`]: crate::BumpAllocator
+/// [`Allocator`]: alloc::alloc::Allocator
+/// [`GenericBump`]: crate::generic::GenericBump
+macro_rules! alloc_methods {
+    (
+        impl[$($gen:tt)*] $name:ident$(<$($param:tt),*>)?,
+        field = $field:tt,
+        chunk_size = $size:literal,
+        chunk_align = $align:literal,
+        $(errors = [$($errors:literal),* $(,)?],)?
+    ) => {
+        impl<$($gen)*> $name$(<$($param),*>)? {
+            /// Tries to allocate memory with a size and alignment matching
+            /// `layout`.
+            ///
+            /// Returns a pointer to the memory on success, or [`None`] on
+            /// failure.
+            #[doc = concat!(
+                "The memory is valid until the [`", stringify!($name),
+                "`] is dropped.",
+            )]
+            /// Note that the returned memory could be larger than
+            /// [`layout.size()`].
+            ///
+            /// This method is similar to [`Allocator::allocate`], except it
+            /// returns an [`Option`] instead of a [`Result`].
+            ///
+            #[doc = concat!(
+                "Allocation is guaranteed to succeed, assuming the global ",
+                "allocator succeeds, if [`layout.size()`] is less than or ",
+                "equal to ", $size, " and [`layout.align()`] is less than or ",
+                "equal to ", $align, ". See [`Self::can_allocate`].",
+            )]
+            ///
+            /// [`layout.size()`]: core::alloc::Layout::size
+            /// [`layout.align()`]: core::alloc::Layout::align
+            /// [`Allocator::allocate`]: alloc::alloc::Allocator::allocate
+            pub fn allocate(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Option<core::ptr::NonNull<[u8]>> {
+                self.$field.allocate(layout)
+            }
+
+            /// Like [`Self::allocate`], but returns an error describing why
+            /// the allocation failed instead of [`None`].
+            ///
+            /// # Errors
+            ///
+            /// If allocation fails, an [`AllocFailure`] is returned. This
+            /// makes it possible to tell, for example, an allocation that can
+            /// never fit in this allocator's chunks
+            /// ([`AllocFailure::TooLarge`]) from a failure of the global
+            /// allocator ([`AllocFailure::OutOfMemory`]).
+            $($(#[doc = $errors])*)?
+            ///
+            /// [`AllocFailure`]: crate::AllocFailure
+            /// [`AllocFailure::TooLarge`]: crate::AllocFailure::TooLarge
+            /// [`AllocFailure::OutOfMemory`]: crate::AllocFailure::OutOfMemory
+            pub fn try_allocate(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, $crate::AllocFailure> {
+                self.$field.try_allocate(layout)
+            }
+
+            /// Allocates a value of type `T`.
+            ///
+            /// The memory is initialized with `value` and a reference to the
+            /// value is returned. Note that the value's destructor will not be
+            /// called automatically.
+            ///
+            /// # Panics
+            ///
+            /// Panics if this allocator cannot allocate memory matching
+            /// [`Layout::new::<T>()`] (see [`Self::can_allocate`]). Note that
+            /// if the global allocator fails, [`handle_alloc_error`] is called
+            /// instead of panicking.
+            ///
+            /// For an equivalent that doesn't panic or call
+            /// [`handle_alloc_error`], see [`Self::try_alloc_value`].
+            ///
+            /// [`Layout::new::<T>()`]: core::alloc::Layout::new
+            /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+            #[allow(clippy::mut_from_ref)]
+            #[must_use]
+            pub fn alloc_value<T>(&self, value: T) -> &mut T {
+                self.$field.alloc_value(value)
+            }
+
+            /// Tries to allocate a value of type `T`.
+            ///
+            /// If the allocation succeeds, the memory is initialized with
+            /// `value` and a reference to the value is returned. Note that the
+            /// value's destructor will not be called automatically.
+            ///
+            /// Allocation succeeds if and only if [`Self::allocate`] is able
+            /// to allocate memory matching [`Layout::new::<T>()`]. See
+            /// [`Self::allocate`] for details regarding the circumstances in
+            /// which allocation can fail.
+            ///
+            /// # Errors
+            ///
+            /// If allocation fails, <code>[Err]\(value)</code> is returned.
+            ///
+            /// [`Layout::new::<T>()`]: core::alloc::Layout::new
+            #[allow(clippy::mut_from_ref)]
+            pub fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
+                self.$field.try_alloc_value(value)
+            }
+
+            /// Returns whether this allocator can allocate memory matching
+            /// `layout`.
+            ///
+            #[doc = concat!(
+                "This is guaranteed to return true if [`layout.size()`] is ",
+                "less than or equal to ", $size, " and [`layout.align()`] is ",
+                "less than or equal to ", $align, ". It *may* return true if ",
+                "the alignment is bigger, but never if the size is.",
+            )]
+            ///
+            /// [`layout.size()`]: core::alloc::Layout::size
+            /// [`layout.align()`]: core::alloc::Layout::align
+            pub fn can_allocate(&self, layout: core::alloc::Layout) -> bool {
+                self.$field.can_allocate(layout)
+            }
+        }
+
+        $crate::methods::allocator_impls! {
+            impl[$($gen)*] $name$(<$($param),*>)?,
+        }
+    };
+}
+
+pub(crate) use alloc_methods;
+
+/// Generates the inherent methods shared by the bump allocators, along with
+/// their [`BumpAllocator`] and [`Allocator`] implementations (see
+/// [`alloc_methods!`]).
+///
+/// The type must be a tuple struct whose only field is a [`GenericBump`].
+/// `chunk_size` and `chunk_align` describe the size and alignment of the
+/// chunks in the documentation, `set_layout` names the method (if any) that
+/// changes the chunk layout, and `example` contains the lines of the doc
+/// examples that create an allocator with chunks of at least 32 bytes.
+///
+/// [`BumpAllocator`]: crate::BumpAllocator
+/// [`Allocator`]: alloc::alloc::Allocator
+/// [`GenericBump`]: crate::generic::GenericBump
+macro_rules! bump_methods {
+    (
+        impl[$($gen:tt)*] $name:ident$(<$($param:tt),*>)?,
+        chunk_size = $size:literal,
+        chunk_align = $align:literal,
+        $(set_layout = $set_layout:ident,)?
+        example = [$($example:literal),* $(,)?] $(,)?
+    ) => {
+        $crate::methods::alloc_methods! {
+            impl[$($gen)*] $name$(<$($param),*>)?,
+            field = 0,
+            chunk_size = $size,
+            chunk_align = $align,
+            errors = [
+                " Reaching a limit set by [`Self::set_limit`],",
+                " [`Self::set_max_chunks`], or [`Self::set_budget`] results",
+                " in [`AllocFailure::BudgetExceeded`].",
+                "",
+                "[`AllocFailure::BudgetExceeded`]:",
+                "    crate::AllocFailure::BudgetExceeded",
+            ],
+        }
+
+        impl<$($gen)*> $name$(<$($param),*>)? {
+            /// The number of bytes left in the current chunk, or zero if there
+            /// is no current chunk. Allocating memory whose size is at most
+            /// this value doesn't start a new chunk (see
+            /// [`Self::would_start_new_chunk`]), but padding needed for
+            /// alignment may use up some of the remaining space.
+            pub fn remaining_in_current_chunk(&self) -> usize {
+                self.0.remaining_in_current_chunk()
+            }
+
+            /// Returns whether allocating memory matching `layout` with
+            /// [`Self::allocate`] (or any method or trait implementation that
+            /// uses it, like [`Self::alloc_value`]) would start a new chunk.
+            ///
+            /// New chunks are taken from the spare chunks (see
+            /// [`Self::spare_chunks`]) or the allocator's pool, if any, and
+            /// are otherwise allocated with the global allocator. This returns
+            /// false if the allocation would fail without trying to obtain a
+            /// new chunk, for example because [`Self::can_allocate`] returns
+            /// false or the allocator is sealed.
+            pub fn would_start_new_chunk(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> bool {
+                self.0.would_start_new_chunk(layout)
+            }
+
+            #[doc = concat!(
+                "The maximum size in bytes of a chunk (", $size, "). This is ",
+                "the largest allocation that can succeed.",
+            )]
+            pub fn chunk_capacity(&self) -> usize {
+                self.0.chunk_capacity()
+            }
+
+            /// The page size with which new chunks are prefaulted, or [`None`]
+            /// if prefaulting is disabled (the default). See
+            /// [`Self::set_prefault`].
+            pub fn prefault(&self) -> Option<usize> {
+                self.0.prefault()
+            }
+
+            /// Enables or disables prefaulting of new chunks.
+            ///
+            /// Memory freshly obtained from the global allocator is often not
+            /// yet mapped by the operating system, so the first write to each
+            /// page triggers a page fault, which would otherwise happen during
+            /// whichever allocation first uses that page. If `page_size` is
+            /// not [`None`], every `page_size`-byte page of a new chunk is
+            /// written to as soon as the chunk is allocated, which moves this
+            /// cost to the allocation that starts the chunk. This is most
+            /// effective together with [`Self::reserve_chunks`]: spare chunks
+            /// reserved while prefaulting is enabled are prefaulted when they
+            /// are reserved, outside of latency-sensitive code.
+            ///
+            /// `page_size` should usually be the operating system's page size,
+            /// such as 4096.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `page_size` is <code>[Some]\(0)</code>.
+            pub fn set_prefault(&mut self, page_size: Option<usize>) {
+                self.0.set_prefault(page_size);
+            }
+
+            /// Allocates spare chunks until there are at least `n` of them.
+            ///
+            /// When the current chunk is full, the allocator takes a spare
+            /// chunk, if there is one, instead of calling the global
+            /// allocator, whose latency is unbounded. By reserving chunks
+            /// ahead of time (and topping them up outside of
+            /// latency-sensitive code, with the help of
+            /// [`Self::spare_chunks`]), allocations can avoid the global
+            /// allocator entirely.
+            ///
+            /// Memory in spare chunks is not reclaimed until the allocator is
+            /// dropped.
+            $(#[doc = concat!(
+                "Spare chunks are deallocated, however, if [`Self::",
+                stringify!($set_layout), "`] changes the chunk layout.",
+            )])?
+            ///
+            /// # Panics
+            ///
+            /// Panics if the allocator is sealed (see [`Self::seal`]), or if
+            /// reserving the chunks would exceed a limit set by
+            /// [`Self::set_limit`], [`Self::set_max_chunks`], or
+            /// [`Self::set_budget`]. If the global allocator fails,
+            /// [`handle_alloc_error`] is called.
+            ///
+            /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+            pub fn reserve_chunks(&self, n: usize) {
+                self.0.reserve_chunks(n);
+            }
+
+            /// The number of spare chunks that have been allocated but not yet
+            /// used. See [`Self::reserve_chunks`].
+            pub fn spare_chunks(&self) -> usize {
+                self.0.spare_chunks()
+            }
+
+            /// The maximum total size of this allocator's chunks, if any. See
+            /// [`Self::set_limit`].
+            pub fn limit(&self) -> Option<usize> {
+                self.0.limit()
+            }
+
+            /// Sets the maximum total size of this allocator's chunks, or
+            /// removes the limit if `bytes` is [`None`].
+            ///
+            /// The full size of each chunk is counted, including a small
+            /// amount of bookkeeping data stored alongside it. Spare chunks
+            /// (see [`Self::reserve_chunks`]) count towards the limit. Once
+            /// the limit is reached, no new chunks are obtained:
+            /// [`Self::allocate`] returns [`None`], [`Self::try_alloc_value`]
+            /// returns an error, the [`Allocator`] implementation returns
+            /// [`AllocError`], and [`Self::alloc_value`] panics instead of
+            /// calling [`handle_alloc_error`]. [`Self::try_allocate`] reports
+            /// these failures as [`AllocFailure::BudgetExceeded`].
+            ///
+            /// Chunks that have already been obtained are not affected, even
+            /// if they exceed the new limit. See also
+            /// [`Self::set_max_chunks`] and [`Self::set_budget`].
+            ///
+            /// [`Allocator`]: alloc::alloc::Allocator
+            /// [`AllocError`]: alloc::alloc::AllocError
+            /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+            /// [`AllocFailure::BudgetExceeded`]:
+            ///     crate::AllocFailure::BudgetExceeded
+            pub fn set_limit(&mut self, bytes: Option<usize>) {
+                self.0.set_limit(bytes);
+            }
+
+            /// The maximum number of chunks this allocator can use, if any.
+            /// See [`Self::set_max_chunks`].
+            pub fn max_chunks(&self) -> Option<usize> {
+                self.0.max_chunks()
+            }
+
+            /// Sets the maximum number of chunks this allocator can use, or
+            /// removes the limit if `n` is [`None`].
+            ///
+            /// Spare chunks count towards the limit. Once the limit is
+            /// reached, allocation fails as described in [`Self::set_limit`].
+            pub fn set_max_chunks(&mut self, n: Option<usize>) {
+                self.0.set_max_chunks(n);
+            }
+
+            /// The shared budget this allocator draws from, if any. See
+            /// [`Self::set_budget`].
+            #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+            #[cfg_attr(
+                feature = "doc_cfg",
+                doc(cfg(all(feature = "alloc", target_has_atomic = "ptr")))
+            )]
+            pub fn budget(&self) -> Option<&$crate::Budget> {
+                self.0.budget()
+            }
+
+            /// Sets the shared budget this allocator draws from, or stops
+            /// using a budget if `budget` is [`None`].
+            ///
+            /// The size of each new chunk is drawn from the budget, which can
+            /// be shared with other allocators. Once the budget is exhausted,
+            /// allocation fails as described in [`Self::set_limit`]. The
+            /// chunks' sizes are returned to the budget when the allocator is
+            /// dropped.
+            ///
+            /// The sizes of the chunks this allocator already has are moved
+            /// from the old budget to the new one, even if that makes the new
+            /// budget exceed its limit.
+            #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+            #[cfg_attr(
+                feature = "doc_cfg",
+                doc(cfg(all(feature = "alloc", target_has_atomic = "ptr")))
+            )]
+            pub fn set_budget(&mut self, budget: Option<$crate::Budget>) {
+                self.0.set_budget(budget);
+            }
+
+            /// The total size of the chunks this allocator currently has,
+            /// including spare chunks and the bookkeeping data stored
+            /// alongside each chunk. This is the amount counted against the
+            /// limits set by [`Self::set_limit`] and [`Self::set_budget`].
+            pub fn chunk_bytes(&self) -> usize {
+                self.0.chunk_bytes()
+            }
+
+            /// Like [`Self::allocate`], but only tries to allocate in the
+            /// current chunk.
+            ///
+            /// Returns [`None`] instead of moving on to a new chunk (whether a
+            /// spare chunk or one from the global allocator) if `layout`
+            /// doesn't fit in the remaining space of the current chunk, or if
+            /// no chunk has been allocated yet. This lets latency-sensitive
+            /// code detect that the current chunk is exhausted and defer work
+            /// that would need more memory.
+            pub fn try_allocate_in_current_chunk(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Option<core::ptr::NonNull<[u8]>> {
+                self.0.allocate_in_current(layout)
+            }
+
+            /// Like [`Self::try_alloc_value`], but only tries to allocate in
+            /// the current chunk. See [`Self::try_allocate_in_current_chunk`].
+            ///
+            /// # Errors
+            ///
+            /// If allocation fails, <code>[Err]\(value)</code> is returned.
+            #[allow(clippy::mut_from_ref)]
+            pub fn try_alloc_value_no_refill<T>(
+                &self,
+                value: T,
+            ) -> Result<&mut T, T> {
+                self.0.try_alloc_value_in_current(value)
+            }
+
+            /// Calls `f` in a critical section, in which allocations must not
+            /// allocate new chunks.
+            ///
+            /// If debug assertions are enabled, any allocation made through
+            /// `self` (the argument passed to `f`) that would need to allocate
+            /// a new chunk from the chunk source (by default, the global
+            /// allocator) panics. Allocations may still use the remaining
+            /// space in the current chunk and any spare chunks (see
+            /// [`Self::reserve_chunks`]). This makes it possible to test that
+            /// latency-sensitive code stays within memory that has already
+            /// been allocated. If debug assertions are disabled, this method
+            /// simply calls `f`.
+            ///
+            /// ```
+            $(#[doc = $example])*
+            /// bump.reserve_chunks(1);
+            /// let array = bump.critical_section(|b| {
+            ///     // Uses the spare chunk, so no new chunk is allocated.
+            ///     b.alloc_value([1_u32; 4])
+            /// });
+            /// assert_eq!(*array, [1; 4]);
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// Panics if debug assertions are enabled and a new chunk would be
+            /// allocated within `f`. Calling [`Self::reserve_chunks`] in `f`
+            /// counts as allocating a new chunk.
+            pub fn critical_section<'a, R>(
+                &'a self,
+                f: impl FnOnce(&'a Self) -> R,
+            ) -> R {
+                self.0.critical_section(|| f(self))
+            }
+
+            /// Seals this allocator: all further allocations fail until
+            /// [`Self::unseal`] is called.
+            ///
+            /// This is useful for enforcing that an allocator is no longer
+            /// used once it has been populated. While sealed,
+            /// [`Self::allocate`] returns [`None`], [`Self::try_alloc_value`]
+            /// returns an error, the [`Allocator`] implementation returns
+            /// [`AllocError`], and [`Self::alloc_value`] panics. Memory that
+            /// was already allocated remains valid.
+            ///
+            /// This is equivalent to
+            /// <code>[self.seal_with]\([SealMode::Fail])</code>.
+            ///
+            /// [`Allocator`]: alloc::alloc::Allocator
+            /// [`AllocError`]: alloc::alloc::AllocError
+            /// [self.seal_with]: Self::seal_with
+            /// [SealMode::Fail]: crate::SealMode::Fail
+            pub fn seal(&self) {
+                self.0.seal($crate::SealMode::Fail);
+            }
+
+            /// Seals this allocator, with `mode` determining what happens when
+            /// memory is allocated while the allocator is sealed. See
+            /// [`Self::seal`].
+            ///
+            /// With [`SealMode::Panic`], allocations panic instead of failing,
+            /// which helps find the source of unexpected allocations.
+            /// [`SealMode::DebugPanic`] does the same only if debug assertions
+            /// are enabled. If the allocator is already sealed, this changes
+            /// the mode.
+            ///
+            /// [`SealMode::Panic`]: crate::SealMode::Panic
+            /// [`SealMode::DebugPanic`]: crate::SealMode::DebugPanic
+            pub fn seal_with(&self, mode: $crate::SealMode) {
+                self.0.seal(mode);
+            }
+
+            /// Unseals this allocator, so that memory can be allocated from it
+            /// again. See [`Self::seal`].
+            pub fn unseal(&mut self) {
+                self.0.unseal();
+            }
+
+            /// Returns whether this allocator is sealed. See [`Self::seal`].
+            pub fn is_sealed(&self) -> bool {
+                self.0.is_sealed()
+            }
+
+            /// Makes the next allocation start at the beginning of a new
+            /// chunk.
+            ///
+            /// This lets related values that are allocated afterwards share a
+            /// chunk, rather than being split between the end of the current
+            /// chunk and the start of the next one. Memory remaining in the
+            /// current chunk is not reused. If nothing has been allocated in
+            /// the current chunk yet, this method does nothing.
+            pub fn start_new_chunk(&self) {
+                self.0.start_new_chunk();
+            }
+
+            /// Calls `f` with an allocation group, in which all allocations
+            /// come from a single chunk.
+            ///
+            /// Allocations made through `self` (the argument passed to `f`)
+            /// start at the beginning of a fresh chunk (see
+            /// [`Self::start_new_chunk`]). Once that chunk is full, further
+            /// allocations in the group fail rather than allocating another
+            /// chunk: [`Self::allocate`] and [`Self::try_alloc_value`] fail,
+            /// and [`Self::alloc_value`] panics. This ensures that values that
+            /// are accessed together are also stored together. A group can
+            /// hold at most [`Self::chunk_capacity`] bytes, minus any padding
+            /// needed for alignment.
+            ///
+            /// If `f` returns an [`Option`], the `?` operator can be used to
+            /// abort the group when an allocation fails:
+            ///
+            /// ```
+            $(#[doc = $example])*
+            /// let group = bump.alloc_group(|g| {
+            ///     let a = g.try_alloc_value(1_u64).ok()?;
+            ///     let b = g.try_alloc_value([2_u64; 3]).ok()?;
+            ///     Some((a, b))
+            /// });
+            /// assert!(group.is_some());
+            ///
+            /// // Doesn't fit in a single chunk.
+            /// let group = bump.alloc_group(|g| {
+            ///     let a = g.try_alloc_value([1_u64; 3]).ok()?;
+            ///     let b = g.try_alloc_value([2_u64; 3]).ok()?;
+            ///     Some((a, b))
+            /// });
+            /// assert!(group.is_none());
+            /// ```
+            ///
+            /// If this method is called inside `f`, the inner call becomes
+            /// part of the existing group.
+            pub fn alloc_group<'a, R>(
+                &'a self,
+                f: impl FnOnce(&'a Self) -> R,
+            ) -> R {
+                self.0.group(|| f(self))
+            }
+
+            /// The cache line size used by isolated allocations (see
+            /// [`Self::allocate_isolated`]). This is initially
+            /// [`CACHE_LINE_SIZE`].
+            ///
+            /// [`CACHE_LINE_SIZE`]: crate::CACHE_LINE_SIZE
+            pub fn cache_line_size(&self) -> usize {
+                self.0.line_size()
+            }
+
+            /// Sets the cache line size used by isolated allocations (see
+            /// [`Self::allocate_isolated`]).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `size` is not a power of two.
+            pub fn set_cache_line_size(&mut self, size: usize) {
+                self.0.set_line_size(size);
+            }
+
+            /// Like [`Self::allocate`], but the returned memory doesn't share
+            /// a cache line with any other allocation in this allocator.
+            ///
+            /// The allocation is padded on both sides and aligned to a
+            /// multiple of [`Self::cache_line_size`]. This avoids false
+            /// sharing when the memory is used by a different thread than its
+            /// neighbors, at the cost of up to about two cache lines of wasted
+            /// space per allocation.
+            ///
+            /// In addition to the conditions in [`Self::allocate`], allocation
+            /// fails if the padded allocation doesn't fit in an empty chunk.
+            /// This can depend on the address of the chunk, unless the chunk
+            /// size is at least [`layout.size()`] plus twice the cache line
+            /// size.
+            ///
+            /// [`layout.size()`]: core::alloc::Layout::size
+            pub fn allocate_isolated(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Option<core::ptr::NonNull<[u8]>> {
+                self.0.allocate_isolated(layout)
+            }
+
+            /// Like [`Self::alloc_value`], but the value doesn't share a cache
+            /// line with any other allocation in this allocator. See
+            /// [`Self::allocate_isolated`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if [`Self::allocate_isolated`] would fail for
+            /// [`Layout::new::<T>()`] for any reason other than the global
+            /// allocator failing, in which case [`handle_alloc_error`] is
+            /// called instead.
+            ///
+            /// [`Layout::new::<T>()`]: core::alloc::Layout::new
+            /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+            #[allow(clippy::mut_from_ref)]
+            #[must_use]
+            pub fn alloc_value_isolated<T>(&self, value: T) -> &mut T {
+                self.0.alloc_value_isolated(value)
+            }
+
+            /// Like [`Self::try_alloc_value`], but the value doesn't share a
+            /// cache line with any other allocation in this allocator. See
+            /// [`Self::allocate_isolated`].
+            ///
+            /// # Errors
+            ///
+            /// If allocation fails, <code>[Err]\(value)</code> is returned.
+            #[allow(clippy::mut_from_ref)]
+            pub fn try_alloc_value_isolated<T>(
+                &self,
+                value: T,
+            ) -> Result<&mut T, T> {
+                self.0.try_alloc_value_isolated(value)
+            }
+        }
+    };
+}
+
+pub(crate) use bump_methods;
+
+/// Generates the methods of the bump allocators whose chunk layout is a
+/// [`ConstChunkLayout`], which check at compile time that values fit in the
+/// chunks.
+///
+/// `layout` is the [`ConstChunkLayout`] type. `chunk_size` and `chunk_align`
+/// are as in [`bump_methods!`]; `too_small` creates an allocator whose chunks
+/// can't hold a [`u64`], and `example` is the type of an allocator with
+/// 64-byte chunks aligned to at least 8 bytes.
+///
+/// [`ConstChunkLayout`]: crate::generic::ConstChunkLayout
+macro_rules! const_layout_methods {
+    (
+        impl[$($gen:tt)*] $name:ident$(<$($param:tt),*>)?,
+        layout = $layout:ty,
+        chunk_size = $size:literal,
+        chunk_align = $align:literal,
+        too_small = $too_small:literal,
+        example = $example:literal $(,)?
+    ) => {
+        impl<$($gen)*> $name$(<$($param),*>)? {
+            /// Like [`Self::alloc_value`], but checks at compile time that `T`
+            /// fits in this allocator's chunks.
+            ///
+            #[doc = concat!(
+                "Compilation fails if [`mem::size_of::<T>()`] is greater ",
+                "than ", $size, ", or if [`mem::align_of::<T>()`] is greater ",
+                "than the alignment of the chunks (which is at least ", $align,
+                "):",
+            )]
+            ///
+            /// ```compile_fail
+            #[doc = concat!("let bump = fixed_bump::", $too_small, ";")]
+            /// let item = bump.alloc_value_checked(1_u64);
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// Panics if the allocator is sealed, if the value doesn't fit in
+            /// the chunk of the current allocation group, or if a memory limit
+            /// is reached. If the global allocator fails,
+            /// [`handle_alloc_error`] is called.
+            ///
+            /// [`mem::size_of::<T>()`]: core::mem::size_of
+            /// [`mem::align_of::<T>()`]: core::mem::align_of
+            /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+            #[allow(clippy::mut_from_ref)]
+            #[must_use]
+            pub fn alloc_value_checked<T>(&self, value: T) -> &mut T {
+                let _ = $crate::generic::Fits::<T, $layout>::OK;
+                self.0.alloc_value(value)
+            }
+
+            /// Allocates uninitialized memory for a value of type `T`,
+            /// checking at compile time that `T` fits in this allocator's
+            /// chunks.
+            ///
+            /// This is equivalent to calling [`Self::alloc_value_checked`]
+            /// with [`MaybeUninit::uninit()`]; see that method for details.
+            ///
+            /// [`MaybeUninit::uninit()`]: core::mem::MaybeUninit::uninit
+            #[allow(clippy::mut_from_ref)]
+            #[must_use]
+            pub fn alloc_fitting<T>(&self) -> &mut core::mem::MaybeUninit<T> {
+                self.alloc_value_checked(core::mem::MaybeUninit::uninit())
+            }
+
+            /// The number of values of type `T` that fit in a single chunk,
+            /// taking into account the padding needed for alignment. This is
+            /// [`usize::MAX`] if `T` is zero-sized, and zero if `T` is more
+            /// aligned than the chunks.
+            ///
+            /// This is a `const fn`, so it can be used in constants:
+            ///
+            /// ```
+            #[doc = concat!("use fixed_bump::", stringify!($name), ";")]
+            #[doc = concat!(
+                "const N: usize = ", $example, "::items_per_chunk::<u32>();",
+            )]
+            /// assert_eq!(N, 16);
+            /// ```
+            pub const fn items_per_chunk<T>() -> usize {
+                $crate::inner::items_per_chunk::<T>(
+                    <$layout as $crate::generic::ConstChunkLayout>::CHUNK,
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use const_layout_methods;
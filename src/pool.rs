@@ -0,0 +1,375 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::chunk::Chunk;
+#[cfg(feature = "alloc")]
+use super::chunk::ChunkList;
+#[cfg(feature = "alloc")]
+use super::source::handle_alloc_error;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+use core::alloc::Layout;
+#[cfg(feature = "alloc")]
+use core::cell::RefCell;
+
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+#[cfg(feature = "std")]
+use std::thread::{self, JoinHandle};
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+/// Allocates chunks with layout `layout` and adds them to `free` until it
+/// holds at least `n` chunks. Returns false if the global allocator fails.
+#[cfg(feature = "alloc")]
+fn fill(free: &mut ChunkList, layout: Layout, n: usize) -> bool {
+    while free.len() < n {
+        let chunk = if let Some(chunk) = Chunk::new(layout) {
+            chunk
+        } else {
+            return false;
+        };
+        // SAFETY: The chunk was just allocated by `Chunk::new`, so it is not
+        // in use.
+        unsafe {
+            free.push(chunk);
+        }
+    }
+    true
+}
+
+#[cfg(feature = "alloc")]
+struct LocalPool {
+    layout: Layout,
+    free: RefCell<ChunkList>,
+}
+
+/// A pool of free chunks that can be shared by multiple bump allocators.
+///
+/// Bump allocators created with a pool (e.g., with [`Bump::with_pool`] or
+/// [`DynamicBump::with_pool`]) take chunks from the pool before falling back
+/// to the global allocator, and return their chunks to the pool when dropped.
+/// This avoids a round trip through the global allocator for every chunk
+/// when many short-lived allocators with the same chunk layout are created.
+///
+/// This type is a reference-counted handle; cloning it produces another
+/// handle to the same pool. The pool's free chunks are deallocated when the
+/// last handle and the last allocator using the pool are dropped. For a pool
+/// that can be shared across threads, see `SyncChunkPool` (requires the crate
+/// feature `std`).
+///
+/// [`Bump::with_pool`]: crate::Bump::with_pool
+/// [`DynamicBump::with_pool`]: crate::DynamicBump::with_pool
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "alloc")))]
+#[derive(Clone)]
+pub struct ChunkPool(Rc<LocalPool>);
+
+#[cfg(feature = "alloc")]
+impl ChunkPool {
+    /// Creates a new, empty [`ChunkPool`] holding chunks with layout
+    /// `layout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`layout.size()`] is 0.
+    ///
+    /// [`layout.size()`]: Layout::size
+    pub fn new(layout: Layout) -> Self {
+        assert!(layout.size() != 0, "`layout.size()` cannot be 0");
+        Self(Rc::new(LocalPool {
+            layout,
+            free: RefCell::new(ChunkList::new()),
+        }))
+    }
+
+    /// The layout of the chunks in this pool.
+    pub fn layout(&self) -> Layout {
+        self.0.layout
+    }
+
+    /// The number of free chunks currently in the pool.
+    pub fn free_chunks(&self) -> usize {
+        self.0.free.borrow().len()
+    }
+
+    /// Allocates chunks until the pool holds at least `n` free chunks.
+    ///
+    /// If the global allocator fails, [`handle_alloc_error`] is called.
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    pub fn reserve(&self, n: usize) {
+        let layout = self.layout();
+        if !fill(&mut self.0.free.borrow_mut(), layout, n) {
+            handle_alloc_error(Chunk::full_layout(layout));
+        }
+    }
+
+    fn take(&self) -> Option<Chunk> {
+        self.0.free.borrow_mut().pop()
+    }
+
+    /// # Safety
+    ///
+    /// See [`Pool::give`].
+    unsafe fn give(&self, chunk: Chunk) {
+        // SAFETY: Checked by caller.
+        unsafe {
+            self.0.free.borrow_mut().push(chunk);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+struct SharedPool {
+    layout: Layout,
+    free: Mutex<ChunkList>,
+    /// Notified whenever a chunk is taken from the pool.
+    taken: Condvar,
+}
+
+#[cfg(feature = "std")]
+impl SharedPool {
+    fn lock(&self) -> MutexGuard<'_, ChunkList> {
+        // A panic can't leave the list in an inconsistent state, so poisoning
+        // can be ignored.
+        self.free.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A thread-safe version of [`ChunkPool`].
+///
+/// Handles to this pool can be sent to other threads, so allocators on
+/// different threads can share free chunks. A background thread that keeps
+/// a minimum number of free chunks ready can be started with
+/// [`Self::spawn_refiller`].
+///
+/// This type is available only when the crate feature `std` is enabled.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+#[derive(Clone)]
+pub struct SyncChunkPool(Arc<SharedPool>);
+
+#[cfg(feature = "std")]
+impl SyncChunkPool {
+    /// Creates a new, empty [`SyncChunkPool`] holding chunks with layout
+    /// `layout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`layout.size()`] is 0.
+    ///
+    /// [`layout.size()`]: Layout::size
+    pub fn new(layout: Layout) -> Self {
+        assert!(layout.size() != 0, "`layout.size()` cannot be 0");
+        Self(Arc::new(SharedPool {
+            layout,
+            free: Mutex::new(ChunkList::new()),
+            taken: Condvar::new(),
+        }))
+    }
+
+    /// The layout of the chunks in this pool.
+    pub fn layout(&self) -> Layout {
+        self.0.layout
+    }
+
+    /// The number of free chunks currently in the pool.
+    pub fn free_chunks(&self) -> usize {
+        self.0.lock().len()
+    }
+
+    /// Allocates chunks until the pool holds at least `n` free chunks.
+    ///
+    /// If the global allocator fails, [`handle_alloc_error`] is called.
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    pub fn reserve(&self, n: usize) {
+        let layout = self.layout();
+        if !fill(&mut self.0.lock(), layout, n) {
+            handle_alloc_error(Chunk::full_layout(layout));
+        }
+    }
+
+    /// Starts a background thread that allocates chunks whenever the pool
+    /// holds fewer than `min_free` free chunks.
+    ///
+    /// This moves calls to the global allocator off the threads that use the
+    /// pool: as long as the refiller keeps up, allocators that need a new
+    /// chunk find one in the pool. The thread is stopped and joined when the
+    /// returned [`Refiller`] is dropped. If the global allocator fails, the
+    /// thread waits briefly and tries again.
+    pub fn spawn_refiller(&self, min_free: usize) -> Refiller {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = thread::spawn({
+            let pool = self.0.clone();
+            let stop = stop.clone();
+            move || refill(&pool, &stop, min_free)
+        });
+        Refiller {
+            pool: self.clone(),
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    fn take(&self) -> Option<Chunk> {
+        let chunk = self.0.lock().pop();
+        if chunk.is_some() {
+            self.0.taken.notify_all();
+        }
+        chunk
+    }
+
+    /// # Safety
+    ///
+    /// See [`Pool::give`].
+    unsafe fn give(&self, chunk: Chunk) {
+        // SAFETY: Checked by caller.
+        unsafe {
+            self.0.lock().push(chunk);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn refill(pool: &SharedPool, stop: &AtomicBool, min_free: usize) {
+    let mut free = pool.lock();
+    loop {
+        // `stop` is always set while the lock is held, so checking it here
+        // and then waiting can't miss a notification.
+        while free.len() >= min_free && !stop.load(Ordering::Relaxed) {
+            free =
+                pool.taken.wait(free).unwrap_or_else(PoisonError::into_inner);
+        }
+        if stop.load(Ordering::Relaxed) {
+            return;
+        }
+        let missing = min_free - free.len();
+        drop(free);
+
+        // Allocate without holding the lock so that other threads can keep
+        // taking chunks in the meantime.
+        let mut new = ChunkList::new();
+        let ok = fill(&mut new, pool.layout, missing);
+        free = pool.lock();
+        while let Some(chunk) = new.pop() {
+            // SAFETY: `new` only contains chunks newly allocated by
+            // `Chunk::new`.
+            unsafe {
+                free.push(chunk);
+            }
+        }
+        if !ok {
+            free = pool
+                .taken
+                .wait_timeout(free, Duration::from_millis(10))
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+}
+
+/// A background thread that keeps a [`SyncChunkPool`] filled.
+///
+/// Returned by [`SyncChunkPool::spawn_refiller`]. Dropping this type stops
+/// the thread and waits for it to finish.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+pub struct Refiller {
+    pool: SyncChunkPool,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for Refiller {
+    fn drop(&mut self) {
+        let guard = self.pool.0.lock();
+        self.stop.store(true, Ordering::Relaxed);
+        drop(guard);
+        self.pool.0.taken.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// A pool that a bump allocator takes chunks from and returns them to.
+///
+/// Without the crate feature `alloc`, pools can't be created, and this enum
+/// has no variants.
+#[derive(Clone)]
+pub enum Pool {
+    #[cfg(feature = "alloc")]
+    Local(ChunkPool),
+    #[cfg(feature = "std")]
+    Sync(SyncChunkPool),
+}
+
+impl Pool {
+    pub fn layout(&self) -> Layout {
+        match *self {
+            #[cfg(feature = "alloc")]
+            Self::Local(ref pool) => pool.layout(),
+            #[cfg(feature = "std")]
+            Self::Sync(ref pool) => pool.layout(),
+        }
+    }
+
+    pub fn take(&self) -> Option<Chunk> {
+        match *self {
+            #[cfg(feature = "alloc")]
+            Self::Local(ref pool) => pool.take(),
+            #[cfg(feature = "std")]
+            Self::Sync(ref pool) => pool.take(),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// * The memory returned by [`Chunk::storage`] must no longer be in use.
+    /// * The chunk must have been allocated by [`Chunk::new`].
+    /// * <code>chunk.[requested_layout()]</code> must equal
+    ///   [`self.layout()`].
+    ///
+    /// [requested_layout()]: Chunk::requested_layout
+    /// [`self.layout()`]: Self::layout
+    #[cfg_attr(
+        not(feature = "alloc"),
+        allow(
+            unused_unsafe,
+            unused_variables,
+            clippy::needless_pass_by_value
+        )
+    )]
+    pub unsafe fn give(&self, chunk: Chunk) {
+        // SAFETY: Checked by caller.
+        unsafe {
+            match *self {
+                #[cfg(feature = "alloc")]
+                Self::Local(ref pool) => pool.give(chunk),
+                #[cfg(feature = "std")]
+                Self::Sync(ref pool) => pool.give(chunk),
+            }
+        }
+    }
+}
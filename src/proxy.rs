@@ -0,0 +1,100 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use core::mem::ManuallyDrop;
+
+/// A type whose size and alignment are the largest of those of `A` and `B`
+/// (with the size rounded up to a multiple of the alignment). Used by
+/// [`chunk_for!`].
+#[doc(hidden)]
+// The fields are never read; only the union's layout matters.
+#[allow(dead_code)]
+pub union ChunkUnion<A, B> {
+    a: ManuallyDrop<A>,
+    b: ManuallyDrop<B>,
+}
+
+/// Produces a type that can be used as the `Size` parameter of [`Bump`] (and
+/// [`Bump`]'s `Align` parameter, which defaults to `Size`) to make each chunk
+/// large and aligned enough to hold any one of several arrays.
+///
+/// Each argument has the form `n * T`, where `n` is a single token (such as an
+/// integer literal, a constant, or a braced expression). The resulting type is
+/// as large as the largest of the arrays `[T; n]`, and as aligned as the most
+/// aligned of them.
+///
+/// # Example
+///
+/// ```
+/// use fixed_bump::{Bump, chunk_for};
+///
+/// struct Node([u64; 4]);
+/// struct Edge(u32, u32);
+///
+/// // Each chunk can hold 128 `Node`s or 64 `Edge`s (or some mix of them).
+/// let bump = Bump::<chunk_for!(128 * Node, 64 * Edge)>::new();
+/// let node = bump.alloc_value_checked(Node([1, 2, 3, 4]));
+/// let edge = bump.alloc_value_checked(Edge(5, 6));
+/// assert_eq!(node.0[3], 4);
+/// assert_eq!(edge.1, 6);
+///
+/// // The chunks can be given a larger alignment with a marker type:
+/// type Chunk = chunk_for!(128 * Node, 64 * Edge);
+/// let bump = Bump::<Chunk, fixed_bump::Align4096>::new();
+/// # let _ = bump.alloc_value(Edge(1, 2));
+/// ```
+///
+/// [`Bump`]: crate::Bump
+#[macro_export]
+macro_rules! chunk_for {
+    ($n:tt * $t:ty $(,)?) => {
+        [$t; $n]
+    };
+    ($n:tt * $t:ty, $($rest:tt)+) => {
+        $crate::ChunkUnion<[$t; $n], $crate::chunk_for!($($rest)+)>
+    };
+}
+
+macro_rules! define_align {
+    ($($name:ident = $align:literal,)*) => {
+        $(
+            #[doc = concat!(
+                "A zero-sized type with an alignment of ", $align, " bytes, ",
+                "for use as the `Align` parameter of [`Bump`].\n\n",
+                "[`Bump`]: crate::Bump",
+            )]
+            #[repr(align($align))]
+            #[derive(Clone, Copy, Debug, Default)]
+            pub struct $name;
+        )*
+    };
+}
+
+define_align! {
+    Align8 = 8,
+    Align16 = 16,
+    Align32 = 32,
+    Align64 = 64,
+    Align128 = 128,
+    Align256 = 256,
+    Align512 = 512,
+    Align1024 = 1024,
+    Align2048 = 2048,
+    Align4096 = 4096,
+}
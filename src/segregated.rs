@@ -0,0 +1,352 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#[cfg(target_has_atomic = "ptr")]
+use super::budget::Budget;
+use super::error::AllocFailure;
+use super::generic::GenericBump;
+use super::methods::allocator_impls;
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+/// A size class for [`SegregatedBump`].
+///
+/// Allocations with a size of at most [`Self::max_size`] are routed to a
+/// class, which allocates them in chunks with layout [`Self::chunk_layout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeClass {
+    max_size: usize,
+    chunk_layout: Layout,
+}
+
+impl SizeClass {
+    /// Creates a new [`SizeClass`] for allocations of up to `max_size` bytes,
+    /// which will be allocated in chunks with layout `chunk_layout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is greater than [`chunk_layout.size()`].
+    ///
+    /// [`chunk_layout.size()`]: Layout::size
+    pub fn new(max_size: usize, chunk_layout: Layout) -> Self {
+        assert!(
+            max_size <= chunk_layout.size(),
+            "`max_size` must not exceed the chunk size",
+        );
+        Self {
+            max_size,
+            chunk_layout,
+        }
+    }
+
+    /// The maximum size of allocations routed to this class.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// The layout of the chunks used by this class.
+    pub fn chunk_layout(&self) -> Layout {
+        self.chunk_layout
+    }
+}
+
+/// A bump allocator that routes allocations to one of several internal bump
+/// allocators depending on their size.
+///
+/// Each of the `N` internal allocators corresponds to a [`SizeClass`], which
+/// has its own fixed chunk layout. An allocation is handled by the first
+/// class whose [`max_size`] is at least the size of the allocation. This
+/// avoids having to size every chunk for the largest object, and avoids
+/// wasting large amounts of space at the end of chunks when small and large
+/// objects are mixed.
+///
+/// Each allocation is still performed in non-amortized O(1) time, as finding
+/// the right class takes at most `N` comparisons. All memory is freed when
+/// the [`SegregatedBump`] is dropped.
+///
+/// [`max_size`]: SizeClass::max_size
+pub struct SegregatedBump<const N: usize> {
+    classes: [SizeClass; N],
+    bumps: [GenericBump<Layout>; N],
+}
+
+impl<const N: usize> SegregatedBump<N> {
+    /// Creates a new [`SegregatedBump`] with the given size classes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `classes` is not sorted in increasing order of
+    /// [`max_size`](SizeClass::max_size).
+    pub fn new(classes: [SizeClass; N]) -> Self {
+        assert!(
+            classes.windows(2).all(|w| w[0].max_size < w[1].max_size),
+            "size classes must be sorted by increasing `max_size`",
+        );
+        Self {
+            classes,
+            bumps: classes.map(|c| GenericBump::new(c.chunk_layout)),
+        }
+    }
+
+    /// The size classes passed to [`Self::new`].
+    pub fn classes(&self) -> &[SizeClass; N] {
+        &self.classes
+    }
+
+    fn class(&self, layout: Layout) -> Option<&GenericBump<Layout>> {
+        let index =
+            self.classes.iter().position(|c| layout.size() <= c.max_size)?;
+        Some(&self.bumps[index])
+    }
+
+    /// Tries to allocate memory with a size and alignment matching `layout`.
+    ///
+    /// Returns a pointer to the memory on success, or [`None`] on failure.
+    /// The memory is valid until the [`SegregatedBump`] is dropped. Note that
+    /// the returned memory could be larger than [`layout.size()`].
+    ///
+    /// This method is similar to [`Allocator::allocate`], except it returns an
+    /// [`Option`] instead of a [`Result`].
+    ///
+    /// Allocation is guaranteed to succeed, assuming the global allocator
+    /// succeeds, if there is a size class whose [`max_size`] is at least
+    /// [`layout.size()`], and [`layout.align()`] is less than or equal to the
+    /// alignment of the first such class's [`chunk_layout`]. See
+    /// [`Self::can_allocate`].
+    ///
+    /// [`layout.size()`]: Layout::size
+    /// [`layout.align()`]: Layout::align
+    /// [`Allocator::allocate`]: alloc::alloc::Allocator::allocate
+    /// [`max_size`]: SizeClass::max_size
+    /// [`chunk_layout`]: SizeClass::chunk_layout
+    pub fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        self.class(layout)?.allocate(layout)
+    }
+
+    /// Like [`Self::allocate`], but returns an error describing why the
+    /// allocation failed instead of [`None`].
+    ///
+    /// # Errors
+    ///
+    /// If allocation fails, an [`AllocFailure`] is returned. If no size class
+    /// is large enough, this is [`AllocFailure::TooLarge`], with the chunk
+    /// layout of the largest class. Otherwise, it is the failure reported by
+    /// the class the allocation was routed to: for example,
+    /// [`AllocFailure::OutOfMemory`] if the global allocator failed, or
+    /// [`AllocFailure::BudgetExceeded`] if a limit set by
+    /// [`Self::set_limit`], [`Self::set_max_chunks`], or [`Self::set_budget`]
+    /// was reached.
+    pub fn try_allocate(
+        &self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocFailure> {
+        match self.class(layout) {
+            Some(bump) => bump.try_allocate(layout),
+            None => Err(AllocFailure::TooLarge {
+                requested: layout,
+                chunk: self
+                    .classes
+                    .last()
+                    .map_or(Layout::new::<()>(), |c| c.chunk_layout),
+            }),
+        }
+    }
+
+    /// Allocates a value of type `T`.
+    ///
+    /// The memory is initialized with `value` and a reference to the value is
+    /// returned. Note that the value's destructor will not be called
+    /// automatically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this allocator cannot allocate memory matching
+    /// [`Layout::new::<T>()`] (see [`Self::can_allocate`]). Note that if the
+    /// global allocator fails, [`handle_alloc_error`] is called instead of
+    /// panicking.
+    ///
+    /// For an equivalent that doesn't panic or call [`handle_alloc_error`],
+    /// see [`Self::try_alloc_value`].
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    #[allow(clippy::mut_from_ref)]
+    #[must_use]
+    pub fn alloc_value<T>(&self, value: T) -> &mut T {
+        if let Some(bump) = self.class(Layout::new::<T>()) {
+            return bump.alloc_value(value);
+        }
+        panic!("this allocator cannot allocate values of this type");
+    }
+
+    /// Tries to allocate a value of type `T`.
+    ///
+    /// If the allocation succeeds, the memory is initialized with `value` and
+    /// a reference to the value is returned. Note that the value's destructor
+    /// will not be called automatically.
+    ///
+    /// Allocation succeeds if and only if [`Self::allocate`] is able to
+    /// allocate memory matching [`Layout::new::<T>()`]. See [`Self::allocate`]
+    /// for details regarding the circumstances in which allocation can fail.
+    ///
+    /// # Errors
+    ///
+    /// If allocation fails, <code>[Err]\(value)</code> is returned.
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T> {
+        match self.class(Layout::new::<T>()) {
+            Some(bump) => bump.try_alloc_value(value),
+            None => Err(value),
+        }
+    }
+
+    /// Returns whether this allocator can allocate memory matching `layout`.
+    ///
+    /// This is guaranteed to return true if there is a size class whose
+    /// [`max_size`] is at least [`layout.size()`], and [`layout.align()`] is
+    /// less than or equal to the alignment of the first such class's
+    /// [`chunk_layout`]. It *may* return true if the alignment is bigger, but
+    /// never if no class is large enough.
+    ///
+    /// [`layout.size()`]: Layout::size
+    /// [`layout.align()`]: Layout::align
+    /// [`max_size`]: SizeClass::max_size
+    /// [`chunk_layout`]: SizeClass::chunk_layout
+    pub fn can_allocate(&self, layout: Layout) -> bool {
+        self.class(layout).map_or(false, |bump| bump.can_allocate(layout))
+    }
+
+    /// Allocates spare chunks until every size class has at least `n` of
+    /// them.
+    ///
+    /// When the current chunk of a class is full, the class takes a spare
+    /// chunk, if there is one, instead of calling the global allocator. See
+    /// [`Bump::reserve_chunks`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserving the chunks would exceed a limit set by
+    /// [`Self::set_limit`], [`Self::set_max_chunks`], or
+    /// [`Self::set_budget`]. If the global allocator fails,
+    /// [`handle_alloc_error`] is called.
+    ///
+    /// [`Bump::reserve_chunks`]: crate::Bump::reserve_chunks
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    pub fn reserve_chunks(&self, n: usize) {
+        for bump in &self.bumps {
+            bump.reserve_chunks(n);
+        }
+    }
+
+    /// The number of spare chunks of each size class that have been
+    /// allocated but not yet used. See [`Self::reserve_chunks`].
+    pub fn spare_chunks(&self) -> [usize; N] {
+        let mut spare = [0; N];
+        for (n, bump) in spare.iter_mut().zip(&self.bumps) {
+            *n = bump.spare_chunks();
+        }
+        spare
+    }
+
+    /// The maximum total size of the chunks of each size class, if any. See
+    /// [`Self::set_limit`].
+    pub fn limit(&self) -> Option<usize> {
+        self.bumps.first()?.limit()
+    }
+
+    /// Sets the maximum total size of the chunks of each size class, or
+    /// removes the limit if `bytes` is [`None`].
+    ///
+    /// The limit applies to each class separately. To limit the total size
+    /// of the chunks of all classes, use [`Self::set_budget`]. Once the limit
+    /// of a class is reached, allocations routed to it fail as described in
+    /// [`Bump::set_limit`].
+    ///
+    /// [`Bump::set_limit`]: crate::Bump::set_limit
+    pub fn set_limit(&mut self, bytes: Option<usize>) {
+        for bump in &mut self.bumps {
+            bump.set_limit(bytes);
+        }
+    }
+
+    /// The maximum number of chunks each size class can use, if any. See
+    /// [`Self::set_max_chunks`].
+    pub fn max_chunks(&self) -> Option<usize> {
+        self.bumps.first()?.max_chunks()
+    }
+
+    /// Sets the maximum number of chunks each size class can use, or removes
+    /// the limit if `n` is [`None`]. Like [`Self::set_limit`], the limit
+    /// applies to each class separately.
+    pub fn set_max_chunks(&mut self, n: Option<usize>) {
+        for bump in &mut self.bumps {
+            bump.set_max_chunks(n);
+        }
+    }
+
+    /// The shared budget this allocator draws from, if any. See
+    /// [`Self::set_budget`].
+    #[cfg(target_has_atomic = "ptr")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "ptr")))]
+    pub fn budget(&self) -> Option<&Budget> {
+        self.bumps.first()?.budget()
+    }
+
+    /// Sets the shared budget this allocator draws from, or stops using a
+    /// budget if `budget` is [`None`].
+    ///
+    /// All size classes draw from the same budget, so this limits the total
+    /// size of the chunks of the whole allocator. See [`Bump::set_budget`].
+    ///
+    /// [`Bump::set_budget`]: crate::Bump::set_budget
+    #[cfg(target_has_atomic = "ptr")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_has_atomic = "ptr")))]
+    pub fn set_budget(&mut self, budget: Option<Budget>) {
+        if let Some((last, rest)) = self.bumps.split_last_mut() {
+            for bump in rest {
+                bump.set_budget(budget.clone());
+            }
+            last.set_budget(budget);
+        }
+    }
+
+    /// The total size of the chunks of all size classes, including spare
+    /// chunks and the bookkeeping data stored alongside each chunk.
+    pub fn chunk_bytes(&self) -> usize {
+        self.bumps.iter().map(GenericBump::chunk_bytes).sum()
+    }
+}
+
+allocator_impls! {
+    impl[const N: usize] SegregatedBump<N>,
+}
+
+#[cfg(any(doc, doctest))]
+/// [`SegregatedBump`] cannot implement [`Clone`], as this would make it
+/// unsound to implement [`Allocator`](alloc::alloc::Allocator).
+///
+/// ```
+/// use fixed_bump::SegregatedBump;
+/// struct Test<T = SegregatedBump<2>>(T);
+/// ```
+///
+/// ```compile_fail
+/// use fixed_bump::SegregatedBump;
+/// struct Test<T: Clone = SegregatedBump<2>>(T);
+/// ```
+mod segregated_bump_does_not_impl_clone {}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#[cfg(feature = "std")]
+use core::alloc::GlobalAlloc;
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+/// A source of memory for the chunks of a bump allocator.
+///
+/// By default, chunks are allocated with the global allocator (see
+/// [`Global`]). Implementing this trait lets chunks come from somewhere else,
+/// such as a NUMA-local pool, a huge-page allocator, or another arena. A bump
+/// allocator returns each chunk to the source it came from when the chunk is
+/// no longer needed.
+///
+/// # Safety
+///
+/// * [`Self::allocate`] must return either [`None`] or a pointer to memory
+///   that matches `layout` and remains valid until it is passed to
+///   [`Self::deallocate`]. Moving the source must not invalidate the memory.
+/// * Neither method may call any method of a bump allocator that uses this
+///   source, as bump allocators call these methods while their internal
+///   state is borrowed.
+pub unsafe trait ChunkSource {
+    /// Allocates memory matching `layout`, or returns [`None`] on failure.
+    /// The size of `layout` is never zero.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Deallocates memory previously returned by [`Self::allocate`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`Self::allocate`] (called on this
+    /// source) with the same `layout`, and must not have been deallocated
+    /// already.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The global allocator, used as a [`ChunkSource`].
+///
+/// This is the default chunk source of all of this crate's bump allocators.
+/// If the crate feature `alloc` is disabled, there is no global allocator, and
+/// this source fails to allocate any memory; use a different source, like
+/// [`BufferSource`], instead.
+///
+/// [`BufferSource`]: crate::BufferSource
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+// SAFETY: `alloc::alloc::alloc` returns memory that matches the given layout
+// and remains valid until passed to `alloc::alloc::dealloc`, and it never
+// calls into any bump allocator.
+#[cfg(feature = "alloc")]
+unsafe impl ChunkSource for Global {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // SAFETY: Callers of `ChunkSource::allocate` never pass layouts with
+        // a size of zero.
+        NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: The caller guarantees `ptr` was returned by
+        // `Self::allocate`, which uses the global allocator, with `layout`.
+        unsafe {
+            alloc::alloc::dealloc(ptr.as_ptr(), layout);
+        }
+    }
+}
+
+// SAFETY: `Self::allocate` never returns any memory.
+#[cfg(not(feature = "alloc"))]
+unsafe impl ChunkSource for Global {
+    fn allocate(&self, _layout: Layout) -> Option<NonNull<u8>> {
+        None
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        unreachable!("`Global` never allocates without the `alloc` feature");
+    }
+}
+
+// SAFETY: `System` returns memory that matches the given layout and remains
+// valid until passed to `System::dealloc`. Unlike `Global`, it never calls
+// into a global allocator, so it can also provide the chunks of a bump
+// allocator that is itself the global allocator (see `GlobalBump`).
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "std")))]
+unsafe impl ChunkSource for std::alloc::System {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // SAFETY: Callers of `ChunkSource::allocate` never pass layouts with
+        // a size of zero.
+        NonNull::new(unsafe { GlobalAlloc::alloc(self, layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: The caller guarantees `ptr` was returned by
+        // `Self::allocate` with `layout`.
+        unsafe {
+            GlobalAlloc::dealloc(self, ptr.as_ptr(), layout);
+        }
+    }
+}
+
+/// Calls [`alloc::alloc::handle_alloc_error`], or panics if the `alloc`
+/// feature is disabled.
+pub fn handle_alloc_error(layout: Layout) -> ! {
+    #[cfg(feature = "alloc")]
+    alloc::alloc::handle_alloc_error(layout);
+    #[cfg(not(feature = "alloc"))]
+    panic!("memory allocation of {} bytes failed", layout.size());
+}
+
+// SAFETY: This impl simply forwards to `S`'s implementation, which must
+// uphold the same requirements.
+unsafe impl<S: ChunkSource + ?Sized> ChunkSource for &S {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        S::allocate(self, layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: Checked by caller.
+        unsafe { S::deallocate(self, ptr, layout) }
+    }
+}
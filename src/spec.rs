@@ -0,0 +1,197 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::chunk::Chunk;
+use super::error::ChunkLayoutError;
+use super::generic::ChunkLayout;
+use core::alloc::Layout;
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// A chunk layout that can be parsed from a string, such as one read from a
+/// configuration file.
+///
+/// The string consists of a size, optionally followed by a unit, and
+/// optionally followed by `@` and an alignment in bytes. The supported units
+/// are `B`, `KiB`, `MiB`, and `GiB`; sizes without a unit are in bytes. If the
+/// alignment is omitted, it is 1. Whitespace around each part is ignored.
+///
+/// Parsing fails if the layout is not suitable for chunks (see
+/// [`ChunkLayoutError`]), so a [`ChunkSpec`] can always be passed to
+/// constructors like [`DynamicBump::try_new`] successfully.
+///
+/// ```
+/// use fixed_bump::{ChunkSpec, DynamicBump};
+/// use std::alloc::Layout;
+///
+/// let spec: ChunkSpec = "64KiB@4096".parse().unwrap();
+/// assert_eq!(spec.layout(), Layout::from_size_align(65536, 4096).unwrap());
+/// let bump = DynamicBump::try_new(spec.layout()).unwrap();
+/// # let _ = bump;
+/// ```
+///
+/// [`DynamicBump::try_new`]: crate::DynamicBump::try_new
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkSpec(Layout);
+
+impl ChunkSpec {
+    /// Creates a new [`ChunkSpec`], checking that `layout` is suitable for
+    /// chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `layout` has a size of zero or is too large.
+    pub fn new(layout: Layout) -> Result<Self, ChunkLayoutError> {
+        Chunk::check_layout(layout)?;
+        Ok(Self(layout))
+    }
+
+    /// The layout of the chunks.
+    pub fn layout(&self) -> Layout {
+        self.0
+    }
+}
+
+impl From<ChunkSpec> for Layout {
+    fn from(spec: ChunkSpec) -> Self {
+        spec.0
+    }
+}
+
+impl ChunkLayout for ChunkSpec {
+    fn chunk_layout(&self) -> Layout {
+        self.layout()
+    }
+}
+
+const UNITS: [(&str, usize); 4] =
+    [("GiB", 1 << 30), ("MiB", 1 << 20), ("KiB", 1 << 10), ("B", 1)];
+
+impl Display for ChunkSpec {
+    /// Formats the spec in a form that can be parsed again, using the
+    /// largest unit that divides the size evenly.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.0.size();
+        let (unit, factor) = UNITS
+            .iter()
+            .copied()
+            .find(|&(_, factor)| size % factor == 0)
+            .unwrap_or(("B", 1));
+        write!(f, "{}{}@{}", size / factor, unit, self.0.align())
+    }
+}
+
+impl FromStr for ChunkSpec {
+    type Err = ParseChunkSpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (size, align) = match s.split_once('@') {
+            Some((size, align)) => (size, Some(align)),
+            None => (s, None),
+        };
+        let size = size.trim();
+        let digits = size.find(|c: char| !c.is_ascii_digit());
+        let (number, unit) = size.split_at(digits.unwrap_or(size.len()));
+        let number: usize =
+            number.parse().map_err(|_| ParseErrorKind::InvalidSize)?;
+        let unit = unit.trim();
+        let factor = if unit.is_empty() {
+            1
+        } else {
+            UNITS
+                .iter()
+                .find(|&&(name, _)| name == unit)
+                .ok_or(ParseErrorKind::UnknownUnit)?
+                .1
+        };
+        let size =
+            number.checked_mul(factor).ok_or(ChunkLayoutError::Overflow)?;
+        let align = match align {
+            Some(align) => align
+                .trim()
+                .parse()
+                .map_err(|_| ParseErrorKind::InvalidAlign)?,
+            None => 1,
+        };
+        let layout = Layout::from_size_align(size, align).map_err(|_| {
+            if align.is_power_of_two() {
+                ParseErrorKind::Layout(ChunkLayoutError::Overflow)
+            } else {
+                ParseErrorKind::InvalidAlign
+            }
+        })?;
+        Ok(Self::new(layout)?)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParseErrorKind {
+    InvalidSize,
+    UnknownUnit,
+    InvalidAlign,
+    Layout(ChunkLayoutError),
+}
+
+/// An error returned when parsing a [`ChunkSpec`] fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseChunkSpecError(ParseErrorKind);
+
+impl ParseChunkSpecError {
+    /// If parsing failed because the layout is not suitable for chunks,
+    /// returns the reason.
+    pub fn layout_error(&self) -> Option<ChunkLayoutError> {
+        match self.0 {
+            ParseErrorKind::Layout(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseErrorKind> for ParseChunkSpecError {
+    fn from(kind: ParseErrorKind) -> Self {
+        Self(kind)
+    }
+}
+
+impl From<ChunkLayoutError> for ParseChunkSpecError {
+    fn from(e: ChunkLayoutError) -> Self {
+        Self(ParseErrorKind::Layout(e))
+    }
+}
+
+impl Display for ParseChunkSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            ParseErrorKind::InvalidSize => f.write_str("invalid chunk size"),
+            ParseErrorKind::UnknownUnit => {
+                f.write_str("unknown unit (expected B, KiB, MiB, or GiB)")
+            }
+            ParseErrorKind::InvalidAlign => {
+                f.write_str("invalid alignment (expected a power of two)")
+            }
+            ParseErrorKind::Layout(e) => Display::fmt(&e, f),
+        }
+    }
+}
+
+#[cfg(has_core_error)]
+impl core::error::Error for ParseChunkSpecError {}
+
+#[cfg(all(feature = "std", not(has_core_error)))]
+impl std::error::Error for ParseChunkSpecError {}
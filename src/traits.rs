@@ -0,0 +1,223 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::error::AllocFailure;
+use super::generic::alloc_failed;
+#[cfg(feature = "alloc")]
+use super::rc::Rc;
+use core::alloc::Layout;
+use core::ptr::{self, NonNull};
+
+/// A bump allocator.
+///
+/// This trait is implemented by all of this crate's allocators, as well as by
+/// `&T` and [`Rc<T>`] where `T` implements it. Unlike [`Allocator`], it is
+/// available on stable Rust and doesn't require any crate features, so code
+/// that is generic over the kind of bump allocator can be written against
+/// it. It also provides helper methods on top of the required ones, like
+/// [`Self::alloc_slice_copy`].
+///
+/// ```
+/// use fixed_bump::{Bump, BumpAllocator, DynamicBump};
+/// use std::alloc::Layout;
+///
+/// fn make_pair<B: BumpAllocator>(bump: &B) -> (&mut u32, &mut [u8]) {
+///     let n = bump.alloc_value(1_u32);
+///     let bytes = bump.alloc_slice_copy(b"abc");
+///     (n, bytes)
+/// }
+///
+/// let bump = Bump::<[u64; 4]>::new();
+/// assert_eq!(make_pair(&bump).1, b"abc");
+/// let bump = DynamicBump::new(Layout::new::<[u64; 4]>());
+/// assert_eq!(*make_pair(&bump).0, 1);
+/// ```
+///
+/// # Safety
+///
+/// Memory returned by [`Self::allocate`] must match the provided layout and
+/// must remain valid until the allocator is dropped. Moving the allocator
+/// must not invalidate it. References returned by [`Self::alloc_value`] and
+/// [`Self::try_alloc_value`] must point to memory that is valid in the same
+/// way.
+///
+/// [`Allocator`]: alloc::alloc::Allocator
+pub unsafe trait BumpAllocator {
+    /// Tries to allocate memory with a size and alignment matching `layout`.
+    /// Returns a pointer to the memory on success, or [`None`] on failure.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>>;
+
+    /// Like [`Self::allocate`], but returns an error describing why the
+    /// allocation failed instead of [`None`].
+    ///
+    /// # Errors
+    ///
+    /// If allocation fails, an [`AllocFailure`] is returned.
+    fn try_allocate(
+        &self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocFailure>;
+
+    /// Allocates a value of type `T`, returning a reference to it. Note that
+    /// the value's destructor will not be called automatically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this allocator cannot allocate memory matching
+    /// [`Layout::new::<T>()`] (see [`Self::can_allocate`]). Note that if the
+    /// global allocator fails, [`handle_alloc_error`] is called instead of
+    /// panicking.
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    #[allow(clippy::mut_from_ref)]
+    fn alloc_value<T>(&self, value: T) -> &mut T;
+
+    /// Tries to allocate a value of type `T`, returning a reference to it.
+    /// Note that the value's destructor will not be called automatically.
+    ///
+    /// # Errors
+    ///
+    /// If allocation fails, <code>[Err]\(value)</code> is returned.
+    #[allow(clippy::mut_from_ref)]
+    fn try_alloc_value<T>(&self, value: T) -> Result<&mut T, T>;
+
+    /// Returns whether this allocator can allocate memory matching `layout`.
+    fn can_allocate(&self, layout: Layout) -> bool;
+
+    /// Tries to allocate a copy of `slice`. Returns [`None`] if allocation
+    /// fails.
+    #[allow(clippy::mut_from_ref)]
+    fn try_alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> Option<&mut [T]> {
+        let memory = self.allocate(Layout::for_value(slice))?;
+        // SAFETY: `Self::allocate` returns memory that matches the layout of
+        // `slice`.
+        Some(unsafe { copy_slice(memory, slice) })
+    }
+
+    /// Allocates a copy of `slice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this allocator cannot allocate memory matching the layout of
+    /// `slice` (see [`Self::can_allocate`]). Note that if the global allocator
+    /// fails, [`handle_alloc_error`] is called instead of panicking.
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    #[allow(clippy::mut_from_ref)]
+    fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &mut [T] {
+        match self.try_allocate(Layout::for_value(slice)) {
+            // SAFETY: `Self::try_allocate` returns memory that matches the
+            // layout of `slice`.
+            Ok(memory) => unsafe { copy_slice(memory, slice) },
+            Err(e) => alloc_failed(e),
+        }
+    }
+
+    /// Allocates a copy of `s`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Self::alloc_slice_copy`].
+    #[allow(clippy::mut_from_ref)]
+    fn alloc_str(&self, s: &str) -> &mut str {
+        let bytes = self.alloc_slice_copy(s.as_bytes());
+        // SAFETY: `bytes` is a copy of `s`, which is valid UTF-8.
+        unsafe { core::str::from_utf8_unchecked_mut(bytes) }
+    }
+}
+
+/// Copies `slice` into `memory`, returning the copy.
+///
+/// # Safety
+///
+/// `memory` must match the layout of `slice` and must remain valid for `'a`.
+unsafe fn copy_slice<'a, T: Copy>(
+    memory: NonNull<[u8]>,
+    slice: &[T],
+) -> &'a mut [T] {
+    let memory = memory.cast::<T>();
+    // SAFETY: The caller guarantees that `memory` matches the layout of
+    // `slice`, so `slice.len()` values of type `T` can be stored in it. The
+    // memory can't overlap `slice`, which is borrowed immutably.
+    unsafe {
+        ptr::copy_nonoverlapping(slice.as_ptr(), memory.as_ptr(), slice.len());
+    }
+    // SAFETY: We just initialized the memory with a copy of `slice`, and the
+    // caller guarantees that it remains valid for `'a`.
+    unsafe {
+        &mut *ptr::slice_from_raw_parts_mut(memory.as_ptr(), slice.len())
+    }
+}
+
+// SAFETY: This impl simply forwards to `T`'s implementation. Memory returned
+// by `T` remains valid until `T` is dropped, which can't happen while this
+// reference exists.
+unsafe impl<T: BumpAllocator> BumpAllocator for &T {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        T::allocate(self, layout)
+    }
+
+    fn try_allocate(
+        &self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocFailure> {
+        T::try_allocate(self, layout)
+    }
+
+    fn alloc_value<V>(&self, value: V) -> &mut V {
+        T::alloc_value(self, value)
+    }
+
+    fn try_alloc_value<V>(&self, value: V) -> Result<&mut V, V> {
+        T::try_alloc_value(self, value)
+    }
+
+    fn can_allocate(&self, layout: Layout) -> bool {
+        T::can_allocate(self, layout)
+    }
+}
+
+// SAFETY: This impl simply forwards to `T`'s implementation. Memory returned
+// by `T` remains valid until the last clone of this `Rc` is dropped, and
+// moving an `Rc` doesn't move the `T` it points to.
+#[cfg(feature = "alloc")]
+unsafe impl<T: BumpAllocator> BumpAllocator for Rc<T> {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        T::allocate(&self.0, layout)
+    }
+
+    fn try_allocate(
+        &self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocFailure> {
+        T::try_allocate(&self.0, layout)
+    }
+
+    fn alloc_value<V>(&self, value: V) -> &mut V {
+        T::alloc_value(&self.0, value)
+    }
+
+    fn try_alloc_value<V>(&self, value: V) -> Result<&mut V, V> {
+        T::try_alloc_value(&self.0, value)
+    }
+
+    fn can_allocate(&self, layout: Layout) -> bool {
+        T::can_allocate(&self.0, layout)
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::{AllocFailure, Budget, Bump, DynamicBump};
+use std::alloc::Layout;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// The number of bytes counted against the limits for each chunk of
+/// `Size`, including the chunk's header.
+fn chunk_bytes<Size>() -> usize {
+    let bump = DynamicBump::new(Layout::new::<Size>());
+    bump.reserve_chunks(1);
+    bump.chunk_bytes()
+}
+
+#[test]
+fn limit() {
+    let chunk = chunk_bytes::<[u32; 2]>();
+    assert!(chunk > 8);
+    let bump = Bump::<[u32; 2]>::with_limit(2 * chunk);
+    assert_eq!(bump.limit(), Some(2 * chunk));
+    for i in 0..4_u32 {
+        assert_eq!(*bump.alloc_value(i), i);
+    }
+    assert_eq!(bump.chunk_bytes(), 2 * chunk);
+    assert!(bump.allocate(Layout::new::<u32>()).is_none());
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<u32>()),
+        Err(AllocFailure::BudgetExceeded { .. }),
+    ));
+    assert_eq!(bump.try_alloc_value(5_u32), Err(5));
+}
+
+#[test]
+fn max_chunks() {
+    let mut bump = DynamicBump::with_max_chunks(Layout::new::<[u8; 4]>(), 1);
+    assert_eq!(bump.max_chunks(), Some(1));
+    assert!(bump.try_alloc_value([1_u8; 4]).is_ok());
+    assert!(bump.try_alloc_value(2_u8).is_err());
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<u8>()),
+        Err(AllocFailure::BudgetExceeded { .. }),
+    ));
+    bump.set_max_chunks(None);
+    assert!(bump.try_allocate(Layout::new::<u8>()).is_ok());
+    assert_eq!(*bump.alloc_value(3_u8), 3);
+}
+
+#[test]
+fn spare_chunks_count() {
+    let bump = Bump::<[u8; 8]>::with_max_chunks(2);
+    bump.reserve_chunks(2);
+    assert_eq!(bump.chunk_bytes(), 2 * chunk_bytes::<[u8; 8]>());
+    let _ = bump.alloc_value([1_u8; 8]);
+    let _ = bump.alloc_value([2_u8; 8]);
+    assert!(bump.try_alloc_value(3_u8).is_err());
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<u8>()),
+        Err(AllocFailure::BudgetExceeded { .. }),
+    ));
+}
+
+#[test]
+fn alloc_value_panics() {
+    let bump = Bump::<u64>::with_limit(chunk_bytes::<u64>());
+    let _ = bump.alloc_value(1_u64);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let _ = bump.alloc_value(2_u64);
+    }));
+    let message = *result.unwrap_err().downcast::<&str>().unwrap();
+    assert_eq!(message, "memory budget exceeded");
+}
+
+#[test]
+fn shared_budget() {
+    let chunk1 = chunk_bytes::<[u32; 2]>();
+    let chunk2 = chunk_bytes::<[u32; 4]>();
+    let budget = Budget::new(chunk1 + chunk2);
+    let bump1 = Bump::<[u32; 2]>::with_budget(budget.clone());
+    let bump2 =
+        DynamicBump::with_budget(Layout::new::<[u32; 4]>(), budget.clone());
+    let _ = bump1.alloc_value(1_u32);
+    assert_eq!(budget.used(), chunk1);
+    let _ = bump2.alloc_value(2_u32);
+    assert_eq!(budget.used(), chunk1 + chunk2);
+    assert_eq!(budget.remaining(), 0);
+    let _ = bump1.alloc_value(3_u32);
+    assert!(bump1.try_alloc_value(4_u32).is_err());
+    assert!(matches!(
+        bump1.try_allocate(Layout::new::<u32>()),
+        Err(AllocFailure::BudgetExceeded { .. }),
+    ));
+    drop(bump2);
+    assert_eq!(budget.used(), chunk1);
+    assert_eq!(*bump1.alloc_value(4_u32), 4);
+    drop(bump1);
+    assert_eq!(budget.used(), 0);
+}
+
+#[test]
+fn set_budget() {
+    let chunk = chunk_bytes::<[u8; 8]>();
+    let budget1 = Budget::new(2 * chunk);
+    let budget2 = Budget::new(4);
+    let mut bump = Bump::<[u8; 8]>::with_budget(budget1.clone());
+    let _ = bump.alloc_value(1_u8);
+    assert_eq!(budget1.used(), chunk);
+    bump.set_budget(Some(budget2.clone()));
+    assert_eq!(budget1.used(), 0);
+    assert_eq!(budget2.used(), chunk);
+    assert_eq!(budget2.remaining(), 0);
+    drop(bump);
+    assert_eq!(budget2.used(), 0);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let bump = Bump::<[u32; 4]>::with_max_chunks(1);
+    let mut vec: Vec<u32, _> = Vec::new_in(&bump);
+    assert!(vec.try_reserve_exact(4).is_ok());
+    vec.extend(0..4);
+    let mut vec2: Vec<u32, _> = Vec::new_in(&bump);
+    assert!(vec2.try_reserve_exact(1).is_err());
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<u32>()),
+        Err(AllocFailure::BudgetExceeded { .. }),
+    ));
+}
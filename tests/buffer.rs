@@ -0,0 +1,145 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::{
+    AllocFailure, BufferSource, Bump, ChunkLayoutError, CustomBump,
+    DynamicBump, FixedBump,
+};
+use std::alloc::Layout;
+use std::mem::MaybeUninit;
+
+#[test]
+fn exhausted() {
+    let mut memory = [MaybeUninit::uninit(); 256];
+    let source = BufferSource::new(Layout::new::<[u32; 4]>());
+    let blocks = source.add_buffer(&mut memory);
+    assert!(blocks > 0);
+    let bump = Bump::<[u32; 4], u32, _>::new_in(&source);
+    for i in 0..blocks as u32 {
+        assert_eq!(*bump.alloc_value([i; 4]), [i; 4]);
+    }
+    assert_eq!(source.free_chunks(), 0);
+    assert!(bump.allocate(Layout::new::<[u32; 4]>()).is_none());
+    assert_eq!(bump.try_alloc_value(1_u32), Err(1));
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<u32>()),
+        Err(AllocFailure::OutOfMemory { .. }),
+    ));
+}
+
+#[test]
+fn reuse() {
+    let mut memory = [MaybeUninit::uninit(); 256];
+    let layout = Layout::new::<[u64; 2]>();
+    let source = BufferSource::with_buffer(layout, &mut memory);
+    let blocks = source.free_chunks();
+    for _ in 0..3 {
+        let bump = DynamicBump::new_in(layout, &source);
+        let mut n = 0;
+        while bump.try_alloc_value([0_u64; 2]).is_ok() {
+            n += 1;
+        }
+        assert_eq!(n, blocks);
+        drop(bump);
+        assert_eq!(source.free_chunks(), blocks);
+    }
+}
+
+#[test]
+fn multiple_buffers() {
+    let mut first = [MaybeUninit::uninit(); 128];
+    let mut second = [MaybeUninit::uninit(); 128];
+    let source = BufferSource::new(Layout::new::<u64>());
+    let n = source.add_buffer(&mut first);
+    let m = source.add_buffer(&mut second);
+    assert_eq!(source.free_chunks(), n + m);
+    assert_eq!(source.add_buffer(&mut []), 0);
+    let bump = Bump::<u64, u64, _>::new_in(&source);
+    let items: Vec<_> =
+        (0..(n + m) as u64).map(|i| bump.alloc_value(i)).collect();
+    assert!(items.iter().enumerate().all(|(i, x)| **x == i as u64));
+    assert_eq!(bump.try_alloc_value(0_u64), Err(0));
+}
+
+#[test]
+fn oversized_chunk() {
+    let mut memory = [MaybeUninit::uninit(); 256];
+    let source =
+        BufferSource::with_buffer(Layout::new::<[u8; 16]>(), &mut memory);
+    let bump = DynamicBump::new_in(Layout::new::<[u8; 32]>(), &source);
+    assert!(bump.allocate(Layout::new::<u8>()).is_none());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "new chunk allocated inside a critical section")]
+fn critical_section() {
+    let mut memory = [MaybeUninit::uninit(); 256];
+    let source =
+        BufferSource::with_buffer(Layout::new::<[u32; 2]>(), &mut memory);
+    let bump = Bump::<[u32; 2], u32, _>::new_in(&source);
+    bump.reserve_chunks(1);
+    bump.critical_section(|b| {
+        let _ = b.alloc_value([1_u32; 2]);
+        let _ = b.alloc_value(2_u32);
+    });
+}
+
+#[test]
+fn try_new_in() {
+    let mut memory = [MaybeUninit::uninit(); 64];
+    let layout = Layout::new::<[u64; 2]>();
+    let source = BufferSource::with_buffer(layout, &mut memory);
+    let bump = DynamicBump::try_new_in(layout, &source).unwrap();
+    assert_eq!(*bump.alloc_value(1_u64), 1);
+    assert!(matches!(
+        DynamicBump::try_new_in(Layout::new::<[u8; 0]>(), &source),
+        Err(ChunkLayoutError::ZeroSize),
+    ));
+}
+
+#[test]
+fn fixed_and_custom() {
+    let mut memory = [MaybeUninit::uninit(); 256];
+    let layout = Layout::new::<[u64; 2]>();
+    let source = BufferSource::with_buffer(layout, &mut memory);
+    let blocks = source.free_chunks();
+    let fixed = FixedBump::<16, 8, _>::new_in(&source);
+    assert_eq!(*fixed.alloc_value([1_u64; 2]), [1; 2]);
+    let custom = CustomBump::new_in(layout, &source);
+    assert_eq!(*custom.alloc_value([2_u64; 2]), [2; 2]);
+    assert_eq!(source.free_chunks(), blocks - 2);
+    drop(fixed);
+    drop(custom);
+    assert_eq!(source.free_chunks(), blocks);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let mut memory = [MaybeUninit::uninit(); 1024];
+    let layout = Layout::new::<[u32; 16]>();
+    let source = BufferSource::with_buffer(layout, &mut memory);
+    let bump = DynamicBump::new_in(layout, &source);
+    let mut vec: Vec<u32, _> = Vec::with_capacity_in(16, &bump);
+    vec.extend(0..16);
+    assert_eq!(vec.iter().sum::<u32>(), 120);
+}
@@ -0,0 +1,139 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::{ChunkLayout, ChunkLayoutError, ChunkSpec, CustomBump};
+use std::alloc::Layout;
+use std::cell::Cell;
+
+struct Config {
+    chunk_size: Cell<usize>,
+}
+
+impl ChunkLayout for &Config {
+    fn chunk_layout(&self) -> Layout {
+        Layout::from_size_align(self.chunk_size.get(), 8).unwrap()
+    }
+}
+
+#[test]
+fn basic() {
+    let bump = CustomBump::new(Layout::new::<[u32; 4]>());
+    let item1 = bump.alloc_value(1_u32);
+    let item2 = bump.alloc_value(2_u16);
+    let item3 = bump.alloc_value(3_u32);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    assert_eq!(*item3, 3);
+}
+
+#[test]
+fn set_source() {
+    let config = Config {
+        chunk_size: Cell::new(8),
+    };
+    let mut bump = CustomBump::new(&config);
+    assert_eq!(bump.layout(), Layout::new::<u64>());
+    let item1 = bump.alloc_value(1_u64) as *mut u64;
+    assert!(!bump.can_allocate(Layout::new::<[u64; 4]>()));
+
+    let other = Config {
+        chunk_size: Cell::new(32),
+    };
+    let old = bump.set_source(&other);
+    assert!(std::ptr::eq(old, &config));
+    assert_eq!(bump.layout(), Layout::new::<[u64; 4]>());
+    let item2 = bump.alloc_value([2_u64; 4]);
+    assert_eq!(*item2, [2; 4]);
+    assert_eq!(bump.source().chunk_size.get(), 32);
+    // SAFETY: `item1` was allocated by `bump`, which is still alive.
+    assert_eq!(unsafe { *item1 }, 1);
+}
+
+#[test]
+fn live_config() {
+    let config = Config {
+        chunk_size: Cell::new(8),
+    };
+    let bump = CustomBump::new(&config);
+    let item1 = bump.alloc_value(1_u64) as *mut u64;
+    assert!(!bump.can_allocate(Layout::new::<[u64; 4]>()));
+
+    // The layout is read from the source whenever it is needed.
+    config.chunk_size.set(32);
+    assert_eq!(bump.layout(), Layout::new::<[u64; 4]>());
+    let item2 = bump.alloc_value([2_u64; 4]);
+    assert_eq!(*item2, [2; 4]);
+    // SAFETY: `item1` was allocated by `bump`, which is still alive.
+    assert_eq!(unsafe { *item1 }, 1);
+}
+
+struct Reentrant<'a> {
+    bump: &'a Cell<Option<&'a CustomBump<Reentrant<'a>>>>,
+}
+
+impl ChunkLayout for Reentrant<'_> {
+    fn chunk_layout(&self) -> Layout {
+        if let Some(bump) = self.bump.get() {
+            // Layout sources may use the allocator.
+            assert!(bump.remaining_in_current_chunk() <= 16);
+            assert!(!bump.is_sealed());
+        }
+        Layout::new::<[u64; 2]>()
+    }
+}
+
+#[test]
+fn reentrant_source() {
+    let cell = Cell::new(None);
+    let bump = CustomBump::new(Reentrant {
+        bump: &cell,
+    });
+    cell.set(Some(&bump));
+    for i in 0..8_u64 {
+        assert_eq!(*bump.alloc_value(i), i);
+    }
+}
+
+#[test]
+fn try_new() {
+    let spec: ChunkSpec = "64B@8".parse().unwrap();
+    let bump = CustomBump::try_new(spec).unwrap();
+    assert_eq!(bump.layout(), Layout::from_size_align(64, 8).unwrap());
+    assert_eq!(*bump.alloc_value(1_u64), 1);
+    assert!(matches!(
+        CustomBump::try_new(Layout::new::<[u8; 0]>()),
+        Err(ChunkLayoutError::ZeroSize),
+    ));
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let bump = CustomBump::new(Layout::new::<[u32; 16]>());
+    let mut vec: Vec<u32, _> = Vec::with_capacity_in(16, &bump);
+    for i in 0..16 {
+        vec.push(i);
+    }
+    for i in 0..16 {
+        assert_eq!(vec[i as usize], i);
+    }
+}
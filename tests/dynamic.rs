@@ -0,0 +1,140 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::{ChunkLayoutError, ChunkSpec, DynamicBump};
+use std::alloc::Layout;
+
+#[test]
+fn basic() {
+    let bump = DynamicBump::new(Layout::new::<[u32; 4]>());
+    let item1 = bump.alloc_value(1_u32);
+    let item2 = bump.alloc_value(2_u16);
+    let item3 = bump.alloc_value(3_u32);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    assert_eq!(*item3, 3);
+}
+
+#[test]
+fn set_chunk_layout() {
+    let mut bump = DynamicBump::new(Layout::new::<[u8; 2]>());
+    let item1 = bump.alloc_value(1_u8) as *mut u8;
+    assert!(!bump.can_allocate(Layout::new::<[u64; 4]>()));
+
+    bump.set_chunk_layout(Layout::new::<[u64; 4]>());
+    assert_eq!(bump.layout(), Layout::new::<[u64; 4]>());
+    let item2 = bump.alloc_value(2_u8);
+    let item3 = bump.alloc_value([3_u64; 4]);
+    let item4 = bump.alloc_value(4_u64);
+    assert_eq!(*item2, 2);
+    assert_eq!(*item3, [3; 4]);
+    assert_eq!(*item4, 4);
+
+    bump.set_chunk_layout(Layout::new::<u8>());
+    assert!(!bump.can_allocate(Layout::new::<u16>()));
+    let item5 = bump.alloc_value(5_u8);
+    assert_eq!(*item5, 5);
+    // SAFETY: `item1` was allocated by `bump`, which is still alive.
+    assert_eq!(unsafe { *item1 }, 1);
+}
+
+#[test]
+fn reserved_chunks() {
+    let mut bump = DynamicBump::with_reserved_chunks(Layout::new::<u32>(), 2);
+    let _ = bump.alloc_value(1_u32);
+    assert_eq!(bump.spare_chunks(), 1);
+    bump.set_chunk_layout(Layout::new::<u64>());
+    assert_eq!(bump.spare_chunks(), 0);
+    bump.reserve_chunks(3);
+    assert_eq!(bump.spare_chunks(), 3);
+    let item = bump.alloc_value(2_u64);
+    assert_eq!(bump.spare_chunks(), 2);
+    assert_eq!(*item, 2);
+}
+
+#[test]
+fn for_items() {
+    let bump = DynamicBump::for_items::<u64>(3);
+    assert_eq!(bump.layout(), Layout::new::<[u64; 3]>());
+    assert_eq!(bump.chunk_capacity(), 24);
+    assert_eq!(bump.items_per_chunk::<u64>(), 3);
+    assert_eq!(bump.items_per_chunk::<[u8; 5]>(), 4);
+    for i in 0..3 {
+        let _ = bump.alloc_value(i as u64);
+        assert_eq!(bump.remaining_in_current_chunk(), 16 - i * 8);
+    }
+    assert!(bump.would_start_new_chunk(Layout::new::<u64>()));
+}
+
+#[test]
+fn try_new() {
+    let bump = DynamicBump::try_new(Layout::new::<[u32; 4]>()).unwrap();
+    assert_eq!(*bump.alloc_value(1_u32), 1);
+    assert_eq!(
+        DynamicBump::try_new(Layout::new::<[u8; 0]>()).err(),
+        Some(ChunkLayoutError::ZeroSize),
+    );
+    let huge = Layout::from_size_align(isize::MAX as usize - 2, 1).unwrap();
+    assert_eq!(
+        DynamicBump::try_new(huge).err(),
+        Some(ChunkLayoutError::Overflow),
+    );
+}
+
+#[test]
+fn chunk_spec() {
+    let spec: ChunkSpec = "64KiB@4096".parse().unwrap();
+    assert_eq!(spec.layout(), Layout::from_size_align(65536, 4096).unwrap());
+    assert_eq!(spec.to_string(), "64KiB@4096");
+
+    let spec: ChunkSpec = " 100 B ".parse().unwrap();
+    assert_eq!(spec.layout(), Layout::from_size_align(100, 1).unwrap());
+    assert_eq!(spec.to_string(), "100B@1");
+    let spec: ChunkSpec = "3MiB @ 8".parse().unwrap();
+    assert_eq!(spec.layout(), Layout::from_size_align(3 << 20, 8).unwrap());
+    let spec: ChunkSpec = "1536".parse().unwrap();
+    assert_eq!(spec.to_string(), "1536B@1");
+
+    assert!("".parse::<ChunkSpec>().is_err());
+    assert!("KiB".parse::<ChunkSpec>().is_err());
+    assert!("4 kb".parse::<ChunkSpec>().is_err());
+    assert!("4KiB@3".parse::<ChunkSpec>().is_err());
+    assert!("4KiB@".parse::<ChunkSpec>().is_err());
+    let error = "0KiB".parse::<ChunkSpec>().unwrap_err();
+    assert_eq!(error.layout_error(), Some(ChunkLayoutError::ZeroSize));
+    assert_eq!(error.to_string(), "chunk size is zero");
+    let error = "99999999999999999999GiB".parse::<ChunkSpec>().unwrap_err();
+    assert_eq!(error.layout_error(), None);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let bump = DynamicBump::new(Layout::new::<[u32; 16]>());
+    let mut vec: Vec<u32, _> = Vec::with_capacity_in(16, &bump);
+    for i in 0..16 {
+        vec.push(i);
+    }
+    for i in 0..16 {
+        assert_eq!(vec[i as usize], i);
+    }
+}
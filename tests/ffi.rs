@@ -0,0 +1,90 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "ffi")]
+
+use fixed_bump::DynamicBump;
+use fixed_bump::ffi::*;
+use std::alloc::Layout;
+use std::ptr;
+
+#[test]
+fn alloc_and_reset() {
+    let bump = fixed_bump_new(64, 8);
+    assert!(!bump.is_null());
+    let mut stats = Stats::default();
+    // SAFETY: `bump` was returned by `fixed_bump_new` and is freed only at
+    // the end, and allocated memory isn't used after a reset.
+    unsafe {
+        let x = fixed_bump_alloc(bump, 8, 8).cast::<u64>();
+        assert!(!x.is_null());
+        x.write(5);
+        assert_eq!(fixed_bump_last_error(bump), Status::Ok);
+        fixed_bump_get_stats(bump, &mut stats);
+        let chunk = stats.chunk_bytes;
+        assert!(chunk > 64);
+        for _ in 0..4 {
+            assert!(!fixed_bump_alloc(bump, 32, 8).is_null());
+        }
+        assert_eq!(*x, 5);
+        fixed_bump_get_stats(bump, &mut stats);
+        assert_eq!((stats.chunk_size, stats.chunk_align), (64, 8));
+        assert_eq!(stats.chunk_bytes, 3 * chunk);
+        assert_eq!(stats.free_chunks, 0);
+
+        fixed_bump_reset(bump);
+        fixed_bump_get_stats(bump, &mut stats);
+        assert_eq!(stats.chunk_bytes, 0);
+        assert_eq!(stats.free_chunks, 3);
+        assert!(!fixed_bump_alloc(bump, 64, 8).is_null());
+        fixed_bump_get_stats(bump, &mut stats);
+        assert_eq!(stats.free_chunks, 2);
+        fixed_bump_free(bump);
+    }
+}
+
+#[test]
+fn errors() {
+    assert!(fixed_bump_new(0, 8).is_null());
+    assert!(fixed_bump_new(8, 3).is_null());
+    assert!(fixed_bump_new(usize::MAX, 1).is_null());
+    let bump = fixed_bump_new(16, 4);
+    // SAFETY: `bump` was returned by `fixed_bump_new` and is freed only at
+    // the end.
+    unsafe {
+        assert!(fixed_bump_alloc(bump, 17, 1).is_null());
+        assert_eq!(fixed_bump_last_error(bump), Status::TooLarge);
+        assert!(fixed_bump_alloc(bump, 4, 64).is_null());
+        assert_eq!(fixed_bump_last_error(bump), Status::TooLarge);
+        assert!(fixed_bump_alloc(bump, 4, 3).is_null());
+        assert_eq!(fixed_bump_last_error(bump), Status::InvalidLayout);
+        assert!(!fixed_bump_alloc(bump, 4, 4).is_null());
+        assert_eq!(fixed_bump_last_error(bump), Status::Ok);
+        fixed_bump_free(bump);
+        fixed_bump_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn status_from_failure() {
+    let bump = DynamicBump::new(Layout::new::<[u8; 16]>());
+    bump.seal();
+    let failure = bump.try_allocate(Layout::new::<u8>()).unwrap_err();
+    assert_eq!(Status::from(failure), Status::Other);
+}
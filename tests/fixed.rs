@@ -0,0 +1,75 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::FixedBump;
+use std::alloc::Layout;
+
+#[test]
+fn basic() {
+    let bump = FixedBump::<16, 4>::new();
+    let item1 = bump.alloc_value(1_u32);
+    let item2 = bump.alloc_value(2_u16);
+    let item3 = bump.alloc_value(3_u32);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    assert_eq!(*item3, 3);
+}
+
+#[test]
+fn multiple_chunks() {
+    let bump = FixedBump::<8, 4>::with_reserved_chunks(2);
+    let item1 = bump.alloc_value_checked(1_u32);
+    let item2 = bump.alloc_value_checked(2_u32);
+    assert_eq!(bump.spare_chunks(), 1);
+    let item3 = bump.alloc_value_checked(3_u64);
+    assert_eq!(bump.spare_chunks(), 0);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    assert_eq!(*item3, 3);
+}
+
+#[test]
+fn aligned() {
+    let bump = FixedBump::<4096, 64>::new();
+    for _ in 0..128 {
+        let ptr = bump.allocate(Layout::from_size_align(24, 64).unwrap());
+        assert_eq!(ptr.unwrap().as_ptr().cast::<u8>() as usize % 64, 0);
+    }
+    let bump = FixedBump::<8192, 4096>::new();
+    for _ in 0..4 {
+        let ptr = bump.allocate(Layout::from_size_align(4096, 4096).unwrap());
+        assert_eq!(ptr.unwrap().as_ptr().cast::<u8>() as usize % 4096, 0);
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let bump = FixedBump::<64, 4>::new();
+    let mut vec: Vec<u32, _> = Vec::with_capacity_in(16, &bump);
+    for i in 0..16 {
+        vec.push(i);
+    }
+    for i in 0..16 {
+        assert_eq!(vec[i as usize], i);
+    }
+}
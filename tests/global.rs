@@ -0,0 +1,158 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(target_has_atomic = "8")]
+
+use fixed_bump::{Align4096, GlobalBump, Oversized};
+use std::alloc::{GlobalAlloc, Layout};
+use std::mem::MaybeUninit;
+use std::ptr::addr_of_mut;
+use std::thread;
+
+#[test]
+fn lifo() {
+    static mut MEMORY: [MaybeUninit<u8>; 1024] = [MaybeUninit::uninit(); 1024];
+    // SAFETY: `MEMORY` isn't used anywhere else.
+    static ALLOC: GlobalBump<[u64; 8]> = unsafe {
+        GlobalBump::with_region(addr_of_mut!(MEMORY), Oversized::Fail)
+    };
+    let layout = Layout::new::<[u64; 2]>();
+    // SAFETY: `layout` has a non-zero size, and every pointer is deallocated
+    // at most once, with the layout it was allocated with.
+    unsafe {
+        let a = ALLOC.alloc(layout);
+        let b = ALLOC.alloc(layout);
+        assert!(!a.is_null() && !b.is_null());
+        assert_ne!(a, b);
+        ALLOC.dealloc(b, layout);
+        let c = ALLOC.alloc(layout);
+        assert_eq!(b, c);
+        // `a` isn't the most recent allocation, so it isn't freed.
+        ALLOC.dealloc(a, layout);
+        let d = ALLOC.alloc(layout);
+        assert!(d != a && d != c);
+    }
+}
+
+#[test]
+fn region_exhausted() {
+    static mut MEMORY: [MaybeUninit<u8>; 1024] = [MaybeUninit::uninit(); 1024];
+    // SAFETY: `MEMORY` isn't used anywhere else.
+    static ALLOC: GlobalBump<[u64; 8]> = unsafe {
+        GlobalBump::with_region(addr_of_mut!(MEMORY), Oversized::Fail)
+    };
+    let layout = Layout::new::<[u64; 8]>();
+    let mut n = 0;
+    // SAFETY: `layout` has a non-zero size.
+    while !unsafe { ALLOC.alloc(layout) }.is_null() {
+        n += 1;
+    }
+    assert!(n > 0);
+    assert!(n <= 1024 / 64);
+}
+
+#[test]
+fn oversized_fail() {
+    static mut MEMORY: [MaybeUninit<u8>; 1024] = [MaybeUninit::uninit(); 1024];
+    // SAFETY: `MEMORY` isn't used anywhere else.
+    static ALLOC: GlobalBump<[u8; 32], u8> = unsafe {
+        GlobalBump::with_region(addr_of_mut!(MEMORY), Oversized::Fail)
+    };
+    assert_eq!(ALLOC.oversized(), Oversized::Fail);
+    let overaligned = Layout::from_size_align(8, 4096).unwrap();
+    // SAFETY: These layouts have non-zero sizes.
+    unsafe {
+        assert!(ALLOC.alloc(Layout::new::<[u8; 33]>()).is_null());
+        assert!(ALLOC.alloc(overaligned).is_null());
+        assert!(!ALLOC.alloc(Layout::new::<[u8; 32]>()).is_null());
+    }
+}
+
+#[test]
+fn overaligned() {
+    const LEN: usize = 1 << 15;
+    static mut MEMORY: [MaybeUninit<u8>; LEN] = [MaybeUninit::uninit(); LEN];
+    // SAFETY: `MEMORY` isn't used anywhere else.
+    static ALLOC: GlobalBump<[u8; 8192], Align4096> = unsafe {
+        GlobalBump::with_region(addr_of_mut!(MEMORY), Oversized::Fail)
+    };
+    for align in [64, 4096] {
+        let layout = Layout::from_size_align(align, align).unwrap();
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = unsafe { ALLOC.alloc(layout) };
+        assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % align, 0);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn oversized_system() {
+    static ALLOC: GlobalBump<[u64; 4]> = GlobalBump::new(Oversized::System);
+    let small = Layout::new::<u64>();
+    let large = Layout::new::<[u64; 64]>();
+    // SAFETY: These layouts have non-zero sizes, and each pointer is
+    // deallocated once, with the layout it was allocated with.
+    unsafe {
+        let a = ALLOC.alloc(small);
+        let b = ALLOC.alloc(large);
+        assert!(!a.is_null() && !b.is_null());
+        b.cast::<[u64; 64]>().write([7; 64]);
+        assert_eq!(*b.cast::<[u64; 64]>(), [7; 64]);
+        ALLOC.dealloc(b, large);
+        ALLOC.dealloc(a, small);
+    }
+}
+
+#[test]
+fn threads() {
+    const LEN: usize = 1 << 16;
+    static mut MEMORY: [MaybeUninit<u8>; LEN] = [MaybeUninit::uninit(); LEN];
+    // SAFETY: `MEMORY` isn't used anywhere else.
+    static ALLOC: GlobalBump<[u64; 32]> = unsafe {
+        GlobalBump::with_region(addr_of_mut!(MEMORY), Oversized::Fail)
+    };
+    let handles: Vec<_> = (0..4_u64)
+        .map(|t| {
+            thread::spawn(move || {
+                let layout = Layout::new::<u64>();
+                let items: Vec<_> = (0..256)
+                    .map(|i| {
+                        // SAFETY: `layout` has a non-zero size.
+                        let ptr = unsafe { ALLOC.alloc(layout) }.cast::<u64>();
+                        assert!(!ptr.is_null());
+                        // SAFETY: `ptr` points to memory for a `u64`.
+                        unsafe {
+                            ptr.write(t * 1000 + i);
+                        }
+                        ptr as usize
+                    })
+                    .collect();
+                for (i, ptr) in (0..).zip(items) {
+                    // SAFETY: The value was written above, and the memory
+                    // isn't used by any other thread.
+                    assert_eq!(unsafe { *(ptr as *const u64) }, t * 1000 + i);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+
+use fixed_bump::GrowingBump;
+use std::alloc::Layout;
+
+#[test]
+fn basic() {
+    let bump = GrowingBump::new(Layout::new::<[u64; 2]>(), 64);
+    let items: Vec<&mut u64> = (0..32).map(|i| bump.alloc_value(i)).collect();
+    for (i, item) in items.into_iter().enumerate() {
+        assert_eq!(*item, i as u64);
+    }
+}
+
+#[test]
+fn grow_to_fit() {
+    let bump = GrowingBump::new(Layout::new::<u32>(), 64);
+    assert_eq!(bump.max_layout(), Layout::new::<[u32; 16]>());
+    assert!(bump.can_allocate(Layout::new::<[u32; 16]>()));
+    assert!(!bump.can_allocate(Layout::new::<[u32; 17]>()));
+    assert!(!bump.can_allocate(Layout::from_size_align(4, 64).unwrap()));
+
+    let item1 = bump.alloc_value(1_u32);
+    let item2 = bump.alloc_value([2_u32; 12]);
+    let item3 = bump.alloc_value([3_u32; 16]);
+    let item4 = bump.alloc_value(4_u8);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, [2; 12]);
+    assert_eq!(*item3, [3; 16]);
+    assert_eq!(*item4, 4);
+    assert!(bump.try_alloc_value([5_u32; 17]).is_err());
+}
+
+#[test]
+#[should_panic]
+fn max_smaller_than_initial() {
+    let _ = GrowingBump::new(Layout::new::<[u8; 16]>(), 8);
+}
+
+#[test]
+fn growth_sequence() {
+    let bump = GrowingBump::new(Layout::new::<u64>(), 64);
+    let mut sizes = Vec::new();
+    for i in 0_u64..23 {
+        let starts_chunk = bump.remaining_in_current_chunk() < 8;
+        assert_eq!(*bump.alloc_value(i), i);
+        if starts_chunk {
+            sizes.push(bump.remaining_in_current_chunk() + 8);
+        }
+    }
+    assert_eq!(sizes, [8, 16, 32, 64, 64]);
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use fixed_bump::InlineBump;
+use std::alloc::Layout;
+#[cfg(feature = "alloc")]
+use std::mem;
+
+#[test]
+fn basic() {
+    InlineBump::<[u64; 4]>::with_stack_bump(|bump| {
+        let items: Vec<_> = (0..4_u64).map(|i| bump.alloc_value(i)).collect();
+        assert!(items.iter().enumerate().all(|(i, x)| **x == i as u64));
+        assert_eq!(bump.remaining_in_current_chunk(), 0);
+        assert!(!bump.spilled());
+    });
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn spill() {
+    let sum = InlineBump::<[u32; 4], u32>::with_stack_bump(|bump| {
+        let items: Vec<_> = (0..16_u32).map(|i| bump.alloc_value(i)).collect();
+        assert!(bump.spilled());
+        items.into_iter().map(|x| *x).sum::<u32>()
+    });
+    assert_eq!(sum, 120);
+}
+
+#[test]
+fn too_large() {
+    InlineBump::<[u8; 16]>::with_stack_bump(|bump| {
+        assert_eq!(bump.chunk_capacity(), 16);
+        assert!(!bump.can_allocate(Layout::new::<[u8; 17]>()));
+        assert!(bump.allocate(Layout::new::<[u8; 17]>()).is_none());
+        assert_eq!(bump.try_alloc_value(1_u8), Ok(&mut 1));
+        assert!(!bump.spilled());
+    });
+    assert_eq!(InlineBump::<[u64; 8]>::items_per_chunk::<u32>(), 16);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn inline_then_spill() {
+    InlineBump::<[u32; 4], u32>::with_stack_bump(|bump| {
+        let start = bump as *const _ as usize;
+        let inline = start..start + mem::size_of_val(bump);
+        for i in 0_u32..4 {
+            let item = bump.alloc_value(i);
+            assert!(inline.contains(&(item as *mut u32 as usize)));
+            assert!(!bump.spilled());
+        }
+        let item = bump.alloc_value(4_u32);
+        assert!(!inline.contains(&(item as *mut u32 as usize)));
+        assert!(bump.spilled());
+    });
+}
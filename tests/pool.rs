@@ -0,0 +1,140 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::{Bump, ChunkPool, DynamicBump};
+use std::alloc::Layout;
+
+#[test]
+fn chunks_returned_on_drop() {
+    let pool = ChunkPool::new(Layout::new::<[u32; 2]>());
+    let bump = Bump::<[u32; 2]>::with_pool(pool.clone());
+    for i in 0..5_u32 {
+        let _ = bump.alloc_value(i);
+    }
+    assert_eq!(pool.free_chunks(), 0);
+    drop(bump);
+    assert_eq!(pool.free_chunks(), 3);
+
+    let bump = DynamicBump::with_pool(pool.clone());
+    let item1 = bump.alloc_value(1_u32);
+    let item2 = bump.alloc_value(2_u32);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    assert_eq!(pool.free_chunks(), 2);
+    drop(bump);
+    assert_eq!(pool.free_chunks(), 3);
+}
+
+#[test]
+fn reserve() {
+    let pool = ChunkPool::new(Layout::new::<[u64; 4]>());
+    pool.reserve(2);
+    assert_eq!(pool.free_chunks(), 2);
+    let bump = DynamicBump::with_pool(pool.clone());
+    bump.reserve_chunks(1);
+    assert_eq!(pool.free_chunks(), 1);
+    assert_eq!(bump.spare_chunks(), 1);
+    let _ = bump.alloc_value(1_u64);
+    assert_eq!(bump.spare_chunks(), 0);
+    assert_eq!(pool.free_chunks(), 1);
+    drop(bump);
+    assert_eq!(pool.free_chunks(), 2);
+}
+
+#[test]
+fn other_layouts_bypass_pool() {
+    let pool = ChunkPool::new(Layout::new::<[u8; 4]>());
+    let mut bump = DynamicBump::with_pool(pool.clone());
+    let _ = bump.alloc_value(1_u8);
+    bump.set_chunk_layout(Layout::new::<[u8; 8]>());
+    let _ = bump.alloc_value([2_u8; 8]);
+    drop(bump);
+    assert_eq!(pool.free_chunks(), 1);
+}
+
+#[test]
+#[should_panic]
+fn mismatched_layout() {
+    let pool = ChunkPool::new(Layout::new::<[u8; 4]>());
+    let _ = Bump::<[u8; 8]>::with_pool(pool);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sync_pool() {
+    use fixed_bump::SyncChunkPool;
+    use std::thread;
+
+    let pool = SyncChunkPool::new(Layout::new::<[u32; 4]>());
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                for _ in 0..8 {
+                    let bump = Bump::<[u32; 4]>::with_sync_pool(pool.clone());
+                    for i in 0..8_u32 {
+                        assert_eq!(*bump.alloc_value(i), i);
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    let free = pool.free_chunks();
+    assert!((2..=8).contains(&free));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn refiller() {
+    use fixed_bump::SyncChunkPool;
+    use std::thread;
+    use std::time::Duration;
+
+    let pool = SyncChunkPool::new(Layout::new::<[u32; 4]>());
+    let refiller = pool.spawn_refiller(4);
+    while pool.free_chunks() < 4 {
+        thread::sleep(Duration::from_millis(1));
+    }
+    let bump = DynamicBump::with_sync_pool(pool.clone());
+    for i in 0..12_u32 {
+        let _ = bump.alloc_value(i);
+    }
+    drop(refiller);
+    drop(bump);
+    assert!(pool.free_chunks() >= 4);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let pool = ChunkPool::new(Layout::new::<[u32; 16]>());
+    let bump = Bump::<[u32; 16]>::with_pool(pool.clone());
+    let mut vec: Vec<u32, _> = Vec::with_capacity_in(16, &bump);
+    vec.extend(0..16);
+    assert_eq!(vec.iter().sum::<u32>(), 120);
+    drop(vec);
+    drop(bump);
+    assert_eq!(pool.free_chunks(), 1);
+}
@@ -17,6 +17,7 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
+#![cfg(feature = "alloc")]
 #![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 use fixed_bump::{Bump, Rc};
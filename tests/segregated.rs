@@ -0,0 +1,116 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+
+use fixed_bump::{AllocFailure, SegregatedBump, SizeClass};
+use std::alloc::Layout;
+
+fn classes() -> [SizeClass; 2] {
+    [
+        SizeClass::new(8, Layout::new::<[u64; 4]>()),
+        SizeClass::new(256, Layout::new::<[u64; 64]>()),
+    ]
+}
+
+#[test]
+fn basic() {
+    let bump = SegregatedBump::new(classes());
+    assert_eq!(bump.classes(), &classes());
+    let item1 = bump.alloc_value(1_u8);
+    let item2 = bump.alloc_value([2_u64; 32]);
+    let item3 = bump.alloc_value(3_u64);
+    let item4 = bump.alloc_value([4_u8; 9]);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, [2; 32]);
+    assert_eq!(*item3, 3);
+    assert_eq!(*item4, [4; 9]);
+}
+
+#[test]
+fn routing() {
+    let bump = SegregatedBump::new(classes());
+    let small = bump.alloc_value(1_u64) as *mut u64 as usize;
+    let large = bump.alloc_value([2_u64; 2]) as *mut _ as usize;
+    let small2 = bump.alloc_value(3_u64) as *mut u64 as usize;
+    assert_eq!(small - small2, 8);
+    assert!(large.abs_diff(small) >= 8 * 4);
+    assert!(bump.can_allocate(Layout::new::<[u8; 256]>()));
+    assert!(!bump.can_allocate(Layout::new::<[u8; 257]>()));
+    assert!(bump.try_alloc_value([0_u8; 257]).is_err());
+}
+
+#[test]
+fn failures() {
+    let mut bump = SegregatedBump::new(classes());
+    let layout = Layout::new::<[u8; 257]>();
+    assert_eq!(
+        bump.try_allocate(layout),
+        Err(AllocFailure::TooLarge {
+            requested: layout,
+            chunk: Layout::new::<[u64; 64]>(),
+        }),
+    );
+    let layout = Layout::from_size_align(8, 16).unwrap();
+    assert!(matches!(
+        bump.try_allocate(layout),
+        Err(AllocFailure::OverAligned { .. }),
+    ));
+
+    bump.set_max_chunks(Some(1));
+    assert_eq!(bump.max_chunks(), Some(1));
+    bump.reserve_chunks(1);
+    assert_eq!(bump.spare_chunks(), [1, 1]);
+    let bytes = bump.chunk_bytes();
+    for _ in 0..4 {
+        assert!(bump.try_allocate(Layout::new::<u64>()).is_ok());
+    }
+    assert_eq!(bump.spare_chunks(), [0, 1]);
+    let layout = Layout::new::<u64>();
+    assert_eq!(
+        bump.try_allocate(layout),
+        Err(AllocFailure::BudgetExceeded {
+            requested: layout,
+            chunk: Layout::new::<[u64; 4]>(),
+        }),
+    );
+    assert!(bump.try_allocate(Layout::new::<[u8; 16]>()).is_ok());
+    assert_eq!(bump.chunk_bytes(), bytes);
+}
+
+#[test]
+#[should_panic]
+fn unsorted_classes() {
+    let [a, b] = classes();
+    let _ = SegregatedBump::new([b, a]);
+}
+
+#[test]
+fn class_boundaries() {
+    let bump = SegregatedBump::new(classes());
+    let _ = bump.alloc_value([0_u8; 8]);
+    let small = bump.chunk_bytes();
+    let _ = bump.alloc_value([0_u8; 9]);
+    let both = bump.chunk_bytes();
+    assert!(both - small > small);
+    // Both classes have room left, so no more chunks are needed.
+    let _ = bump.alloc_value([1_u8; 8]);
+    let _ = bump.alloc_value([1_u8; 256]);
+    assert_eq!(bump.chunk_bytes(), both);
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright (C) 2026 taylor.fish <contact@taylor.fish>
+ *
+ * This file is part of fixed-bump.
+ *
+ * fixed-bump is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * fixed-bump is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use fixed_bump::{AllocFailure, Bump, ChunkSource, DynamicBump, Global};
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+#[derive(Default)]
+struct Counting {
+    live: Cell<usize>,
+    total: Cell<usize>,
+    max: Option<usize>,
+}
+
+// SAFETY: This forwards to `Global`, and it never calls into any bump
+// allocator.
+unsafe impl ChunkSource for Counting {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if Some(self.total.get()) == self.max {
+            return None;
+        }
+        let ptr = Global.allocate(layout)?;
+        self.live.set(self.live.get() + 1);
+        self.total.set(self.total.get() + 1);
+        Some(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        // SAFETY: Checked by caller.
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn chunks_returned_to_source() {
+    let source = Counting::default();
+    let bump = Bump::<[u32; 2], u32, _>::new_in(&source);
+    for i in 0..5_u32 {
+        let _ = bump.alloc_value(i);
+    }
+    bump.reserve_chunks(2);
+    assert_eq!(source.live.get(), 5);
+    drop(bump);
+    assert_eq!(source.live.get(), 0);
+    assert_eq!(source.total.get(), 5);
+}
+
+#[test]
+fn dynamic() {
+    let source = Counting::default();
+    let mut bump = DynamicBump::new_in(Layout::new::<u64>(), &source);
+    let item = bump.alloc_value(1_u64) as *mut u64;
+    bump.reserve_chunks(1);
+    assert_eq!(source.live.get(), 2);
+    bump.set_chunk_layout(Layout::new::<[u64; 2]>());
+    assert_eq!(source.live.get(), 1);
+    let _ = bump.alloc_value([2_u64; 2]);
+    // SAFETY: `item` was allocated by `bump`, which is still alive.
+    assert_eq!(unsafe { *item }, 1);
+    drop(bump);
+    assert_eq!(source.live.get(), 0);
+}
+
+#[test]
+fn source_failure() {
+    let source = Counting {
+        max: Some(1),
+        ..Counting::default()
+    };
+    let bump = Bump::<u64, u64, _>::new_in(&source);
+    assert!(bump.try_alloc_value(1_u64).is_ok());
+    assert_eq!(bump.try_alloc_value(2_u64), Err(2));
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<u64>()),
+        Err(AllocFailure::OutOfMemory { .. }),
+    ));
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    let source = Counting::default();
+    let bump = DynamicBump::new_in(Layout::new::<[u32; 16]>(), &source);
+    let mut vec: Vec<u32, _> = Vec::with_capacity_in(16, &bump);
+    vec.extend(0..16);
+    assert_eq!(vec.iter().sum::<u32>(), 120);
+    drop(vec);
+    drop(bump);
+    assert_eq!(source.total.get(), 1);
+    assert_eq!(source.live.get(), 0);
+}
@@ -17,9 +17,15 @@
  * along with fixed-bump. If not, see <https://www.gnu.org/licenses/>.
  */
 
+#![cfg(feature = "alloc")]
 #![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
-use fixed_bump::Bump;
+use fixed_bump::{
+    Align16, Align64, Align4096, AllocFailure, Bump, BumpAllocator,
+    ChunkLayoutError, DynamicBump, FixedBump, GrowingBump, Rc, chunk_for,
+};
+use std::alloc::Layout;
+use std::mem;
 
 #[test]
 fn empty() {
@@ -79,6 +85,338 @@ fn multiple_types() {
     assert_eq!(*item11, 11);
 }
 
+#[test]
+fn start_new_chunk() {
+    let bump = Bump::<[u32; 4]>::new();
+    bump.start_new_chunk();
+    let item1 = bump.alloc_value(1_u32) as *mut u32;
+    bump.start_new_chunk();
+    let item2 = bump.alloc_value(2_u32) as *mut u32;
+    let item3 = bump.alloc_value(3_u32) as *mut u32;
+    assert_ne!(item2 as usize, item1 as usize - 4);
+    assert_eq!(item3 as usize, item2 as usize - 4);
+}
+
+#[test]
+fn alloc_group() {
+    let bump = Bump::<[u32; 4]>::new();
+    let _ = bump.alloc_value(0_u32);
+    let items = bump.alloc_group(|g| {
+        let item1 = g.try_alloc_value(1_u32).ok()?;
+        let item2 = g.try_alloc_value([2_u32; 3]).ok()?;
+        Some((item1, item2))
+    });
+    let (item1, item2) = items.unwrap();
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, [2; 3]);
+
+    let result = bump.alloc_group(|g| {
+        g.try_alloc_value([3_u32; 3]).ok()?;
+        g.try_alloc_value([4_u32; 2]).ok()
+    });
+    assert!(result.is_none());
+    let _ = bump.alloc_value([5_u32; 4]);
+}
+
+#[test]
+#[should_panic]
+fn alloc_group_overflow() {
+    let bump = Bump::<[u8; 2]>::new();
+    bump.alloc_group(|g| {
+        for i in 0..3_u8 {
+            let _ = g.alloc_value(i);
+        }
+    });
+}
+
+#[test]
+fn alloc_value_isolated() {
+    let mut bump = Bump::<[u64; 64]>::new();
+    bump.set_cache_line_size(64);
+    assert_eq!(bump.cache_line_size(), 64);
+    let before = bump.alloc_value(0_u8) as *mut u8 as usize;
+    let item1 = bump.alloc_value_isolated(1_u64) as *mut u64 as usize;
+    let item2 = bump.alloc_value_isolated(2_u64) as *mut u64 as usize;
+    let after = bump.alloc_value(3_u8) as *mut u8 as usize;
+    assert_eq!(item1 % 64, 0);
+    assert_eq!(item2 % 64, 0);
+    assert!(before / 64 > item1 / 64);
+    assert!(item1 / 64 > item2 / 64);
+    assert!(item2 / 64 > after / 64);
+    assert!(bump.try_alloc_value_isolated([0_u64; 64]).is_err());
+}
+
+#[test]
+fn reserved_chunks() {
+    let bump = Bump::<[u32; 2]>::with_reserved_chunks(2);
+    assert_eq!(bump.spare_chunks(), 2);
+    let item1 = bump.alloc_value(1_u32);
+    assert_eq!(bump.spare_chunks(), 1);
+    let item2 = bump.alloc_value(2_u32);
+    let item3 = bump.alloc_value(3_u32);
+    assert_eq!(bump.spare_chunks(), 0);
+    bump.reserve_chunks(1);
+    assert_eq!(bump.spare_chunks(), 1);
+    let item4 = bump.alloc_value(4_u32);
+    assert_eq!(bump.spare_chunks(), 1);
+    let item5 = bump.alloc_value(5_u32);
+    assert_eq!(bump.spare_chunks(), 0);
+    bump.reserve_chunks(0);
+    assert_eq!(bump.spare_chunks(), 0);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    assert_eq!(*item3, 3);
+    assert_eq!(*item4, 4);
+    assert_eq!(*item5, 5);
+}
+
+#[test]
+fn no_refill() {
+    let bump = Bump::<[u32; 2]>::new();
+    assert!(bump.try_alloc_value_no_refill(1_u32).is_err());
+    let item1 = bump.alloc_value(1_u32);
+    let item2 = bump.try_alloc_value_no_refill(2_u32).unwrap();
+    assert!(bump.try_alloc_value_no_refill(3_u32).is_err());
+    let layout = std::alloc::Layout::new::<u8>();
+    assert!(bump.try_allocate_in_current_chunk(layout).is_none());
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+}
+
+#[test]
+fn critical_section() {
+    let bump = Bump::<[u32; 2]>::new();
+    let _ = bump.alloc_value(0_u32);
+    bump.reserve_chunks(1);
+    let (item1, item2) = bump.critical_section(|b| {
+        let item1 = b.alloc_value(1_u32);
+        let item2 = b.alloc_value(2_u32);
+        (item1, item2)
+    });
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, 2);
+    let item3 = bump.alloc_value(3_u32);
+    assert_eq!(*item3, 3);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn critical_section_refill() {
+    let bump = Bump::<[u32; 2]>::new();
+    bump.critical_section(|b| {
+        let _ = b.alloc_value(1_u32);
+    });
+}
+
+#[test]
+fn seal() {
+    let mut bump = Bump::<[u32; 4]>::new();
+    let item1 = bump.alloc_value(1_u32) as *mut u32;
+    bump.seal();
+    assert!(bump.is_sealed());
+    assert!(bump.try_alloc_value(2_u32).is_err());
+    assert!(bump.try_alloc_value_no_refill(2_u32).is_err());
+    bump.unseal();
+    assert!(!bump.is_sealed());
+    let item2 = bump.alloc_value(2_u32) as *mut u32;
+    assert_eq!(item2 as usize, item1 as usize - 4);
+}
+
+#[test]
+#[should_panic]
+fn seal_panic() {
+    let bump = Bump::<[u32; 4]>::new();
+    bump.seal_with(fixed_bump::SealMode::Panic);
+    let _ = bump.try_alloc_value(1_u32);
+}
+
+#[test]
+#[should_panic(expected = "allocator is sealed")]
+fn seal_reserve() {
+    let bump = Bump::<[u32; 4]>::new();
+    bump.seal();
+    bump.reserve_chunks(1);
+}
+
+#[test]
+fn prefault() {
+    let mut bump = Bump::<[u8; 10000]>::new();
+    assert_eq!(bump.prefault(), None);
+    bump.set_prefault(Some(4096));
+    assert_eq!(bump.prefault(), Some(4096));
+    bump.reserve_chunks(2);
+    let item1 = bump.alloc_value([1_u8; 10000]);
+    let item2 = bump.alloc_value(2_u8);
+    assert!(item1.iter().all(|&b| b == 1));
+    assert_eq!(*item2, 2);
+}
+
+#[test]
+fn capacity() {
+    const ITEMS: usize = Bump::<[u32; 5]>::items_per_chunk::<u32>();
+    assert_eq!(ITEMS, 5);
+    assert_eq!(Bump::<[u32; 5]>::items_per_chunk::<u64>(), 2);
+    assert_eq!(Bump::<[u8; 3], u8>::items_per_chunk::<()>(), usize::MAX);
+    assert_eq!(Bump::<[u8; 16], u8>::items_per_chunk::<Align64>(), 0);
+
+    let bump = Bump::<[u32; 5]>::new();
+    assert_eq!(bump.chunk_capacity(), 20);
+    assert_eq!(bump.remaining_in_current_chunk(), 0);
+    assert!(bump.would_start_new_chunk(Layout::new::<u32>()));
+    assert!(!bump.would_start_new_chunk(Layout::new::<[u32; 6]>()));
+
+    for i in 0..ITEMS as u32 {
+        assert_eq!(bump.would_start_new_chunk(Layout::new::<u32>()), i == 0);
+        let _ = bump.alloc_value(i);
+    }
+    assert_eq!(bump.remaining_in_current_chunk(), 0);
+    assert!(bump.would_start_new_chunk(Layout::new::<u8>()));
+
+    let _ = bump.alloc_value(1_u8);
+    assert_eq!(bump.remaining_in_current_chunk(), 19);
+    assert!(!bump.would_start_new_chunk(Layout::new::<[u8; 19]>()));
+    assert!(bump.would_start_new_chunk(Layout::new::<[u8; 20]>()));
+    bump.seal();
+    assert!(!bump.would_start_new_chunk(Layout::new::<[u8; 20]>()));
+}
+
+#[test]
+fn alloc_value_checked() {
+    let bump = Bump::<[u64; 2]>::new();
+    let item1 = bump.alloc_value_checked(1_u64);
+    let item2 = bump.alloc_value_checked([2_u32; 4]);
+    let item3 = bump.alloc_fitting::<u16>().write(3);
+    assert_eq!(*item1, 1);
+    assert_eq!(*item2, [2; 4]);
+    assert_eq!(*item3, 3);
+}
+
+#[test]
+fn chunk_for() {
+    type Chunk = chunk_for!(3 * u8, 2 * u16, 1 * [u8; 5]);
+    assert_eq!(mem::size_of::<Chunk>(), 6);
+    assert_eq!(mem::align_of::<Chunk>(), 2);
+    assert_eq!(mem::size_of::<chunk_for!(4 * u32)>(), 16);
+    assert_eq!(mem::align_of::<Align64>(), 64);
+
+    let bump = Bump::<Chunk>::new();
+    let item1 = bump.alloc_value_checked([1_u8; 5]);
+    let item2 = bump.alloc_value_checked([2_u16; 3]);
+    assert_eq!(*item1, [1; 5]);
+    assert_eq!(*item2, [2; 3]);
+    let bump = Bump::<Chunk, Align64>::new();
+    let layout = Layout::from_size_align(6, 64).unwrap();
+    assert!(bump.can_allocate(layout));
+    let ptr = bump.allocate(layout).unwrap();
+    assert_eq!(ptr.as_ptr().cast::<u8>() as usize % 64, 0);
+}
+
+#[test]
+fn overaligned() {
+    let bump = Bump::<[u8; 256], Align64>::new();
+    for _ in 0..8 {
+        let ptr = bump.allocate(Layout::from_size_align(64, 64).unwrap());
+        assert_eq!(ptr.unwrap().as_ptr().cast::<u8>() as usize % 64, 0);
+    }
+    let bump = Bump::<[u8; 4096], Align4096>::new();
+    for _ in 0..3 {
+        let ptr = bump.allocate(Layout::from_size_align(4096, 4096).unwrap());
+        assert_eq!(ptr.unwrap().as_ptr().cast::<u8>() as usize % 4096, 0);
+    }
+    let bump = Bump::<[u8; 48], Align16>::new();
+    for _ in 0..6 {
+        let ptr = bump.allocate(Layout::from_size_align(8, 16).unwrap());
+        assert_eq!(ptr.unwrap().as_ptr().cast::<u8>() as usize % 16, 0);
+    }
+}
+
+fn use_bump<B: BumpAllocator>(bump: B) {
+    let item = bump.alloc_value(1_u32);
+    let slice = bump.alloc_slice_copy(&[2_u16, 3, 4]);
+    let string = bump.alloc_str("abc");
+    assert_eq!(*bump.try_alloc_value(5_u8).unwrap(), 5);
+    assert!(bump.can_allocate(Layout::new::<[u8; 16]>()));
+    assert!(!bump.can_allocate(Layout::new::<[u8; 4096]>()));
+    assert!(bump.try_alloc_slice_copy(&[0_u8; 4096]).is_none());
+    assert!(matches!(
+        bump.try_allocate(Layout::new::<[u8; 4096]>()),
+        Err(AllocFailure::TooLarge { .. }),
+    ));
+    assert_eq!(bump.try_alloc_value([0_u8; 4096]), Err([0; 4096]));
+    assert_eq!(*item, 1);
+    assert_eq!(slice, [2, 3, 4]);
+    assert_eq!(string, "abc");
+}
+
+#[test]
+fn bump_allocator() {
+    use_bump(Bump::<[u64; 2]>::new());
+    let dynamic = DynamicBump::new(Layout::new::<[u64; 2]>());
+    use_bump(&dynamic);
+    assert!(dynamic.chunk_bytes() > 0);
+    use_bump(FixedBump::<16, 8>::new());
+    use_bump(Rc::new(GrowingBump::new(Layout::new::<[u64; 2]>(), 64)));
+}
+
+#[test]
+fn try_allocate() {
+    let bump = Bump::<[u32; 2]>::new();
+    let chunk = Layout::new::<[u32; 2]>();
+    assert!(bump.try_allocate(Layout::new::<u32>()).is_ok());
+
+    let requested = Layout::new::<[u32; 3]>();
+    let failure = bump.try_allocate(requested).unwrap_err();
+    assert_eq!(failure, AllocFailure::TooLarge {
+        requested,
+        chunk,
+    });
+    assert_eq!(failure.requested(), requested);
+    assert_eq!(failure.chunk_layout(), chunk);
+    assert_eq!(
+        failure.to_string(),
+        "allocation of 12 bytes is larger than the chunk size of 8 bytes",
+    );
+
+    let requested = Layout::from_size_align(4, 64).unwrap();
+    assert_eq!(
+        bump.try_allocate(requested),
+        Err(AllocFailure::OverAligned {
+            requested,
+            chunk,
+        }),
+    );
+
+    bump.seal();
+    let requested = Layout::new::<u32>();
+    assert_eq!(
+        bump.try_allocate(requested),
+        Err(AllocFailure::Sealed {
+            requested,
+            chunk,
+        }),
+    );
+
+    let bump = DynamicBump::new(Layout::new::<[u8; 0]>());
+    let failure = bump.try_allocate(requested).unwrap_err();
+    assert!(matches!(failure, AllocFailure::ZeroSizedChunk { .. }));
+    assert!(bump.try_allocate(Layout::new::<()>()).is_ok());
+}
+
+#[test]
+fn try_allocate_budget() {
+    let bump = Bump::<u32>::with_max_chunks(1);
+    let requested = Layout::new::<u32>();
+    assert!(bump.try_allocate(requested).is_ok());
+    assert_eq!(
+        bump.try_allocate(requested),
+        Err(AllocFailure::BudgetExceeded {
+            requested,
+            chunk: requested,
+        }),
+    );
+}
+
 #[cfg(feature = "allocator_api")]
 #[test]
 fn allocator() {
@@ -108,10 +446,9 @@ fn allocator() {
 }
 
 #[test]
-#[should_panic]
-fn zero_chunk_size() {
-    let bump = Bump::<[u8; 0]>::new();
-    for i in 0..4 {
-        let _ = bump.alloc_value(i);
-    }
+fn zero_sized_chunk() {
+    assert_eq!(
+        DynamicBump::try_new(Layout::new::<[u8; 0]>()).err(),
+        Some(ChunkLayoutError::ZeroSize),
+    );
 }